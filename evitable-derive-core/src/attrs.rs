@@ -26,6 +26,9 @@ impl Attrs {
             for item in items {
               match item {
                 NestedMeta::Literal(l) => return Err(Error::unexpected_lit_type(&l).with_span(&l)),
+                NestedMeta::Arm(a) => {
+                  return Err(Error::unexpected_type("match arm").with_span(&a))
+                }
                 NestedMeta::Meta(m) => match m.path().single_ident() {
                   None => return Err(Error::unexpected_type("path").with_span(m.path())),
                   Some(ident) => {
@@ -86,6 +89,19 @@ impl Attrs {
     Ok(ret)
   }
 
+  pub fn get_all<T: FromMeta, S: AsRef<str>>(&mut self, name: S) -> Result<Vec<T>> {
+    let name = name.as_ref();
+    let mut ret = Vec::new();
+    for attr in self.attrs.iter_mut() {
+      if attr.name == name {
+        ret.push(FromMeta::from_meta(&attr.meta)?);
+        attr.used = true;
+      }
+    }
+
+    Ok(ret)
+  }
+
   pub fn ensure_used(&self) -> Result<()> {
     for attr in self.attrs.iter() {
       if !attr.used {
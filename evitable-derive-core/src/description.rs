@@ -37,10 +37,39 @@ impl FromMeta for FieldRef {
   }
 }
 
+/// A format argument of a description. Either a field, or
+/// `unredacted(field)` to show a redacted field in internal rendering.
+#[derive(Debug)]
+pub(crate) struct DescriptionArg {
+  field: FieldRef,
+  unredacted: bool,
+}
+
+impl FromMeta for DescriptionArg {
+  fn from_nested_meta(item: &NestedMeta) -> Result<Self> {
+    match item {
+      NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("unredacted") => {
+        let nested: Vec<&NestedMeta> = list.nested.iter().collect();
+        match nested.as_slice() {
+          [field] => Ok(DescriptionArg {
+            field: FieldRef::from_nested_meta(field)?,
+            unredacted: true,
+          }),
+          _ => Err(Error::unsupported_format("list").with_span(item)),
+        }
+      }
+      _ => Ok(DescriptionArg {
+        field: FieldRef::from_nested_meta(item)?,
+        unredacted: false,
+      }),
+    }
+  }
+}
+
 #[derive(Debug)]
 pub(crate) struct FormatExpression {
   format: LitStr,
-  args: Vec<FieldRef>,
+  args: Vec<DescriptionArg>,
 }
 
 #[derive(Debug)]
@@ -81,15 +110,32 @@ impl FromMeta for Description {
   }
 }
 
+struct ResolvedArg {
+  expr: TokenStream,
+  redact: Option<Redact>,
+  unredacted: bool,
+}
+
+impl ResolvedArg {
+  fn tokens(&self, unredacted: bool) -> TokenStream {
+    let expr = &self.expr;
+    match &self.redact {
+      Some(_) if unredacted && self.unredacted => expr.clone(),
+      Some(Redact(text)) => quote! { ::evitable::Redacted::__new(&#expr, #text) },
+      None => expr.clone(),
+    }
+  }
+}
+
 pub(crate) struct ResolvedDescription {
   format: LitStr,
-  args: Vec<TokenStream>,
+  args: Vec<ResolvedArg>,
 }
 
 impl Description {
-  fn resolve(
+  fn resolve<'a>(
     self,
-    lookup: impl Fn(FieldRef) -> Result<TokenStream>,
+    lookup: impl Fn(FieldRef) -> Result<(TokenStream, &'a ErrorField)>,
   ) -> Result<ResolvedDescription> {
     match self {
       Description::String(s) => Ok(ResolvedDescription {
@@ -97,16 +143,35 @@ impl Description {
         args: Vec::with_capacity(0),
       }),
 
-      Description::FormatExpression(f) => Ok(ResolvedDescription {
-        format: f.format,
-        args: f.args.into_iter().map(lookup).collect::<Result<Vec<_>>>()?,
-      }),
+      Description::FormatExpression(f) => {
+        let mut args = Vec::with_capacity(f.args.len());
+        for DescriptionArg { field, unredacted } in f.args {
+          let span = field.span();
+          let (expr, field) = lookup(field)?;
+          if unredacted && field.redact.is_none() {
+            return Err(
+              Error::custom("`unredacted` can only be used with redacted fields").with_span(&span),
+            );
+          }
+
+          args.push(ResolvedArg {
+            expr,
+            redact: field.redact.clone(),
+            unredacted,
+          });
+        }
+
+        Ok(ResolvedDescription {
+          format: f.format,
+          args,
+        })
+      }
     }
   }
 
-  pub fn resolve_from_inst<'a, T, I: IntoIdent<'a>>(
+  pub fn resolve_from_inst<'a, I: IntoIdent<'a>>(
     self,
-    fields: &Fields<T>,
+    fields: &Fields<ErrorField>,
     ident: I,
   ) -> Result<ResolvedDescription> {
     let ident = &ident.into_ident();
@@ -120,7 +185,7 @@ impl Description {
         FieldRef::Index(i) => Err(Error::unknown_field(i.base10_digits()).with_span(&i)),
         FieldRef::Ident(i) => match f.iter().find(|(f, _)| f.to_string() == i.to_string()) {
           None => Err(Error::unknown_field(&i.to_string()).with_span(&i)),
-          Some(_) => Ok(quote! { #ident.#i }),
+          Some((_, field)) => Ok((quote! { #ident.#i }, field)),
         },
       }),
       Fields::Unnamed(f) => self.resolve(|r| match r {
@@ -130,13 +195,13 @@ impl Description {
           .find(|(f, _)| *f as u64 == i.base10_parse().unwrap())
         {
           None => Err(Error::unknown_field(i.base10_digits()).with_span(&i)),
-          Some(_) => Ok(quote! { #ident.#i }),
+          Some((_, field)) => Ok((quote! { #ident.#i }, field)),
         },
       }),
     }
   }
 
-  pub fn resolve_from_variant<T>(self, fields: &Fields<T>) -> Result<ResolvedDescription> {
+  pub fn resolve_from_variant(self, fields: &Fields<ErrorField>) -> Result<ResolvedDescription> {
     match fields {
       Fields::Unit => self.resolve(|r| match r {
         FieldRef::Ident(i) => Err(Error::unknown_field(&i.to_string()).with_span(&i)),
@@ -146,7 +211,7 @@ impl Description {
         FieldRef::Index(i) => Err(Error::unknown_field(i.base10_digits()).with_span(&i)),
         FieldRef::Ident(i) => match f.iter().find(|(f, _)| f.to_string() == i.to_string()) {
           None => Err(Error::unknown_field(&i.to_string()).with_span(&i)),
-          Some(_) => Ok(quote! { #i }),
+          Some((_, field)) => Ok((quote! { #i }, field)),
         },
       }),
       Fields::Unnamed(f) => self.resolve(|r| match r {
@@ -156,9 +221,9 @@ impl Description {
           .find(|(f, _)| *f as u64 == i.base10_parse().unwrap())
         {
           None => Err(Error::unknown_field(i.base10_digits()).with_span(&i)),
-          Some((i, _)) => Ok({
+          Some((i, field)) => Ok({
             let ident = i.into_ident();
-            quote! { #ident }
+            (quote! { #ident }, field)
           }),
         },
       }),
@@ -166,18 +231,35 @@ impl Description {
   }
 }
 
-impl ToTokens for ResolvedDescription {
-  fn to_tokens(&self, tokens: &mut TokenStream) {
+impl ResolvedDescription {
+  /// Check if the description uses any redacted fields.
+  pub fn is_redacted(&self) -> bool {
+    self.args.iter().any(|a| a.redact.is_some())
+  }
+
+  /// Render the description for internal use, showing redacted fields
+  /// marked with `unredacted(field)`.
+  pub fn unredacted(&self) -> TokenStream {
+    self.write(true)
+  }
+
+  fn write(&self, unredacted: bool) -> TokenStream {
     let format = &self.format;
     if self.args.len() == 0 {
-      tokens.extend(quote! {
+      quote! {
         f.write_str(#format)
-      });
+      }
     } else {
-      let args = &self.args;
-      tokens.extend(quote! {
+      let args = self.args.iter().map(|a| a.tokens(unredacted));
+      quote! {
         write!(f, #format, #(#args),*)
-      });
+      }
     }
   }
 }
+
+impl ToTokens for ResolvedDescription {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    tokens.extend(self.write(false));
+  }
+}
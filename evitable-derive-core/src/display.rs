@@ -56,4 +56,83 @@ impl<'a> DisplayImpl<'a> {
   pub fn for_type(ty: &'a ErrorType) -> Self {
     Self { ty }
   }
+
+  /// The `fmt_unredacted` method of [ErrorContext](evitable::ErrorContext),
+  /// if any description uses redacted fields.
+  pub fn unredacted_method(&self) -> TokenStream {
+    let body = match &self.ty.data {
+      ErrorData::Struct(struct_data) if struct_data.description.is_redacted() => {
+        struct_data.description.unredacted()
+      }
+      ErrorData::Enum(variants) if variants.iter().any(|v| v.description.is_redacted()) => {
+        let ty = &self.ty.ident;
+        let cases = variants.iter().map(|v| {
+          let ident = &v.ident;
+          let destruct = v.destruct();
+          let desc = v.description.unredacted();
+          quote! { #ty::#ident #destruct => #desc }
+        });
+
+        quote! {
+          match self {
+            #(#cases,)*
+          }
+        }
+      }
+      _ => return TokenStream::new(),
+    };
+
+    quote! {
+      fn fmt_unredacted(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        #body
+      }
+    }
+  }
+
+  /// The `fmt_user_message` method of [ErrorContext](evitable::ErrorContext),
+  /// if a user message is set for the type or any variant. Variants without
+  /// one fall back to the message of the type, then to the generic message.
+  pub fn user_message_method(&self) -> TokenStream {
+    let body = match &self.ty.data {
+      ErrorData::Struct(struct_data) => match &struct_data.user_message {
+        Some(message) => message.to_token_stream(),
+        None => return TokenStream::new(),
+      },
+      ErrorData::Enum(variants) => {
+        let default = &self.ty.attrs.user_message;
+        if default.is_none() && variants.iter().all(|v| v.user_message.is_none()) {
+          return TokenStream::new();
+        }
+
+        let default = match default {
+          Some(message) => message.to_token_stream(),
+          None => quote! { f.write_str(::evitable::DEFAULT_USER_MESSAGE) },
+        };
+
+        let ty = &self.ty.ident;
+        let cases = variants.iter().map(|v| {
+          let ident = &v.ident;
+          match &v.user_message {
+            Some(message) => {
+              let destruct = v.destruct();
+              quote! { #ty::#ident #destruct => #message }
+            }
+            None => quote! { #ty::#ident { .. } => #default },
+          }
+        });
+
+        quote! {
+          match self {
+            #(#cases,)*
+          }
+        }
+      }
+    };
+
+    quote! {
+      fn fmt_user_message(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        #body
+      }
+    }
+  }
 }
@@ -2,7 +2,7 @@ use super::*;
 
 #[derive(Clone)]
 pub(crate) struct FromImpl {
-  path: Path,
+  ty: Type,
 }
 
 impl FromImpl {
@@ -12,23 +12,7 @@ impl FromImpl {
     mod_name: &Ident,
     ty: &Ident,
   ) -> Result<FromImplFor> {
-    let ctor = match &struct_data.fields {
-      Fields::Unit => Constructor::Unit,
-      Fields::Named(f) if f.len() == 0 => Constructor::Named,
-      Fields::Unnamed(f) if f.len() == 0 => Constructor::Unnamed,
-      Fields::Named(_) => {
-        return Err(
-          Error::unsupported_shape("Can't derive From for context types that has fields.")
-            .with_span(&self.path),
-        )
-      }
-      Fields::Unnamed(_) => {
-        return Err(
-          Error::unsupported_shape("Can't derive From for context types that has fields.")
-            .with_span(&self.path),
-        )
-      }
-    };
+    let ctor = Constructor::for_struct(struct_data, &self.ty)?;
 
     Ok(FromImplFor {
       from_impl: self.clone(),
@@ -44,23 +28,7 @@ impl FromImpl {
     mod_name: &Ident,
     ty: &Ident,
   ) -> Result<FromImplFor> {
-    let ctor = match &variant.fields {
-      Fields::Unit => Constructor::VariantUnit(variant.ident.clone()),
-      Fields::Named(f) if f.len() == 0 => Constructor::VariantNamed(variant.ident.clone()),
-      Fields::Unnamed(f) if f.len() == 0 => Constructor::VariantUnnamed(variant.ident.clone()),
-      Fields::Named(_) => {
-        return Err(
-          Error::unsupported_shape("Can't derive From for context types that has fields.")
-            .with_span(&self.path),
-        )
-      }
-      Fields::Unnamed(_) => {
-        return Err(
-          Error::unsupported_shape("Can't derive From for context types that has fields.")
-            .with_span(&self.path),
-        )
-      }
-    };
+    let ctor = Constructor::for_variant(variant, &self.ty)?;
 
     Ok(FromImplFor {
       from_impl: self.clone(),
@@ -74,12 +42,16 @@ impl FromImpl {
 impl FromMeta for FromImpl {
   fn from_path(value: &Path) -> Result<Self> {
     Ok(FromImpl {
-      path: value.clone(),
+      ty: <Type as FromMeta>::from_path(value)?,
     })
   }
+
+  fn from_type(value: &Type) -> Result<Self> {
+    Ok(FromImpl { ty: value.clone() })
+  }
 }
 
-enum Constructor {
+pub(crate) enum Constructor {
   Unit,                  /* struct Foo; */
   Named,                 /* struct Foo{}; */
   Unnamed,               /* struct Foo(); */
@@ -88,6 +60,51 @@ enum Constructor {
   VariantUnnamed(Ident), /* enum Foo { Variant() } */
 }
 
+impl Constructor {
+  pub fn for_struct<S: Spanned>(struct_data: &ErrorStruct, span: &S) -> Result<Self> {
+    match &struct_data.fields {
+      Fields::Unit => Ok(Constructor::Unit),
+      Fields::Named(f) if f.is_empty() => Ok(Constructor::Named),
+      Fields::Unnamed(f) if f.is_empty() => Ok(Constructor::Unnamed),
+      Fields::Named(_) => Err(
+        Error::unsupported_shape("Can't derive From for context types that has fields.")
+          .with_span(span),
+      ),
+      Fields::Unnamed(_) => Err(
+        Error::unsupported_shape("Can't derive From for context types that has fields.")
+          .with_span(span),
+      ),
+    }
+  }
+
+  pub fn for_variant<S: Spanned>(variant: &ErrorVariant, span: &S) -> Result<Self> {
+    match &variant.fields {
+      Fields::Unit => Ok(Constructor::VariantUnit(variant.ident.clone())),
+      Fields::Named(f) if f.is_empty() => Ok(Constructor::VariantNamed(variant.ident.clone())),
+      Fields::Unnamed(f) if f.is_empty() => Ok(Constructor::VariantUnnamed(variant.ident.clone())),
+      Fields::Named(_) => Err(
+        Error::unsupported_shape("Can't derive From for context types that has fields.")
+          .with_span(span),
+      ),
+      Fields::Unnamed(_) => Err(
+        Error::unsupported_shape("Can't derive From for context types that has fields.")
+          .with_span(span),
+      ),
+    }
+  }
+
+  pub fn inst(&self, owner: &Ident) -> TokenStream {
+    match self {
+      Constructor::Unit => quote! { #owner },
+      Constructor::Named => quote! { (#owner {}) },
+      Constructor::Unnamed => quote! { (#owner()) },
+      Constructor::VariantUnit(v) => quote! { #owner::#v },
+      Constructor::VariantNamed(v) => quote! { (#owner::#v {}) },
+      Constructor::VariantUnnamed(v) => quote! { (#owner::#v()) },
+    }
+  }
+}
+
 pub(crate) struct FromImplFor {
   from_impl: FromImpl,
   mod_name: Ident,
@@ -98,20 +115,13 @@ pub(crate) struct FromImplFor {
 impl<'a> ToTokens for FromImplFor {
   fn to_tokens(&self, tokens: &mut TokenStream) {
     let mod_name = &self.mod_name;
-    let owner = &self.owner;
-    let path = &self.from_impl.path;
-    let inst = match &self.ctor {
-      Constructor::Unit => quote! { #owner },
-      Constructor::Named => quote! { (#owner {}) },
-      Constructor::Unnamed => quote! { (#owner()) },
-      Constructor::VariantUnit(v) => quote! { #owner::#v },
-      Constructor::VariantNamed(v) => quote! { (#owner::#v {}) },
-      Constructor::VariantUnnamed(v) => quote! { (#owner::#v()) },
-    };
+    let ty = &self.from_impl.ty;
+    let inst = self.ctor.inst(&self.owner);
 
     tokens.extend(quote! {
-      impl ::std::convert::From<#path> for #mod_name::Error {
-        fn from(err: #path) -> Self {
+      impl ::std::convert::From<#ty> for #mod_name::Error {
+        #[track_caller]
+        fn from(err: #ty) -> Self {
           ::evitable::ErrorContext::into_error(#inst, err)
         }
       }
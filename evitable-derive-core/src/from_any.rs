@@ -0,0 +1,66 @@
+use super::from::Constructor;
+use super::*;
+
+pub(crate) struct FromAnyImplFor {
+  mod_name: Ident,
+  owner: Ident,
+  ctor: Constructor,
+}
+
+pub(crate) fn for_type(
+  data: &ErrorData,
+  mod_name: &Ident,
+  ty: &Ident,
+) -> Result<Option<FromAnyImplFor>> {
+  let ctor = match data {
+    ErrorData::Struct(s) if s.from_any => Some(Constructor::for_struct(s, ty)?),
+    ErrorData::Struct(_) => None,
+    ErrorData::Enum(variants) => {
+      let mut marked = variants.iter().filter(|v| v.from_any);
+      match (marked.next(), marked.next()) {
+        (None, _) => None,
+        (Some(variant), None) => Some(Constructor::for_variant(variant, &variant.ident)?),
+        (Some(_), Some(duplicate)) => {
+          return Err(Error::duplicate_field("from_any").with_span(&duplicate.ident))
+        }
+      }
+    }
+  };
+
+  Ok(ctor.map(|ctor| FromAnyImplFor {
+    mod_name: mod_name.clone(),
+    owner: ty.clone(),
+    ctor,
+  }))
+}
+
+impl ToTokens for FromAnyImplFor {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let mod_name = &self.mod_name;
+    let inst = self.ctor.inst(&self.owner);
+
+    tokens.extend(quote! {
+      impl ::std::convert::From<::evitable::AnyError> for #mod_name::Error {
+        #[track_caller]
+        fn from(err: ::evitable::AnyError) -> Self {
+          <Self as ::evitable::EvitableError>::new(#inst, Some(err.into_inner()))
+        }
+      }
+
+      impl ::std::convert::From<Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync + 'static>> for #mod_name::Error {
+        #[track_caller]
+        fn from(err: Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync + 'static>) -> Self {
+          <Self as ::evitable::EvitableError>::new(#inst, Some(err))
+        }
+      }
+
+      impl ::std::convert::From<String> for #mod_name::Error {
+        #[track_caller]
+        fn from(message: String) -> Self {
+          let err = Box::<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync + 'static>::from(message);
+          <Self as ::evitable::EvitableError>::new(#inst, Some(err))
+        }
+      }
+    })
+  }
+}
@@ -0,0 +1,109 @@
+use super::*;
+use evitable_syn_meta_ext::MetaArm;
+use syn::{Pat, PatPath};
+
+#[derive(Clone)]
+pub(crate) struct FromEvitableImpl {
+  path: Path,
+  arms: Vec<MetaArm>,
+}
+
+impl FromEvitableImpl {
+  pub fn for_type(&self, mod_name: &Ident) -> FromEvitableImplFor {
+    FromEvitableImplFor {
+      from_impl: self.clone(),
+      mod_name: mod_name.clone(),
+    }
+  }
+}
+
+impl FromMeta for FromEvitableImpl {
+  fn from_list(items: &[&NestedMeta]) -> Result<Self> {
+    let (first, rest) = match items.split_first() {
+      None => return Err(Error::too_few_items(2)),
+      Some(v) => v,
+    };
+
+    let path = <Path as FromMeta>::from_nested_meta(first)?;
+    if rest.is_empty() {
+      return Err(Error::too_few_items(2).with_span(&path));
+    }
+
+    let mut arms = Vec::with_capacity(rest.len());
+    for item in rest {
+      arms.push(<MetaArm as FromMeta>::from_nested_meta(item)?);
+    }
+
+    Ok(FromEvitableImpl { path, arms })
+  }
+}
+
+/// Kind patterns are written relative to the source error kind, so a
+/// single segment path like `NotFound` is rewritten to `__SourceKind::NotFound`.
+/// Lowercase identifiers are left alone so they can still be used as bindings.
+fn qualify_path(path: &mut Path, kind: &Ident) {
+  if path.leading_colon.is_none() && path.segments.len() == 1 {
+    path.segments.insert(0, kind.clone().into());
+  }
+}
+
+fn qualify_pat(pat: &mut Pat, kind: &Ident) {
+  match pat {
+    Pat::Ident(i)
+      if i.subpat.is_none()
+        && i.by_ref.is_none()
+        && i.mutability.is_none()
+        && i.ident.to_string().starts_with(char::is_uppercase) =>
+    {
+      let mut path: Path = i.ident.clone().into();
+      qualify_path(&mut path, kind);
+      *pat = Pat::Path(PatPath {
+        attrs: i.attrs.clone(),
+        qself: None,
+        path,
+      });
+    }
+    Pat::Path(p) if p.qself.is_none() => qualify_path(&mut p.path, kind),
+    Pat::Struct(s) => qualify_path(&mut s.path, kind),
+    Pat::TupleStruct(t) => qualify_path(&mut t.path, kind),
+    Pat::Or(o) => {
+      for case in o.cases.iter_mut() {
+        qualify_pat(case, kind);
+      }
+    }
+    _ => (),
+  }
+}
+
+pub(crate) struct FromEvitableImplFor {
+  from_impl: FromEvitableImpl,
+  mod_name: Ident,
+}
+
+impl ToTokens for FromEvitableImplFor {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let mod_name = &self.mod_name;
+    let path = &self.from_impl.path;
+    let kind = Ident::new("__SourceKind", Span::call_site());
+    let arms = self.from_impl.arms.iter().map(|arm| {
+      let mut pat = arm.pat.clone();
+      qualify_pat(&mut pat, &kind);
+      let body = &arm.body;
+      quote! { #pat => #body, }
+    });
+
+    tokens.extend(quote! {
+      impl ::std::convert::From<#path> for #mod_name::Error {
+        #[track_caller]
+        fn from(err: #path) -> Self {
+          type #kind = <#path as ::evitable::EvitableError>::Kind;
+          let context = match ::evitable::EvitableError::kind(&err) {
+            #(#arms)*
+          };
+
+          ::evitable::ErrorContext::into_error(context, err)
+        }
+      }
+    })
+  }
+}
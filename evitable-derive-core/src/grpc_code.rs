@@ -0,0 +1,73 @@
+use super::*;
+
+/// Status codes that can be used for errors. `Ok` is reserved for success.
+const CODES: &[&str] = &[
+  "Cancelled",
+  "Unknown",
+  "InvalidArgument",
+  "DeadlineExceeded",
+  "NotFound",
+  "AlreadyExists",
+  "PermissionDenied",
+  "ResourceExhausted",
+  "FailedPrecondition",
+  "Aborted",
+  "OutOfRange",
+  "Unimplemented",
+  "Internal",
+  "Unavailable",
+  "DataLoss",
+  "Unauthenticated",
+];
+
+#[derive(Clone, Copy)]
+pub(crate) struct GrpcCode(&'static str);
+
+impl Default for GrpcCode {
+  fn default() -> Self {
+    GrpcCode("Unknown")
+  }
+}
+
+impl FromMeta for GrpcCode {
+  fn from_string<S: Spanned>(value: &str, span: &S) -> Result<Self> {
+    match CODES.iter().find(|c| **c == value) {
+      Some(c) => Ok(GrpcCode(c)),
+      None => Err(Error::unknown_value(value).with_span(span)),
+    }
+  }
+
+  fn from_ident(value: &Ident) -> Result<Self> {
+    let s = value.to_string();
+    Self::from_string(&s, value)
+  }
+}
+
+impl ToTokens for GrpcCode {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let code = Ident::new(self.0, Span::call_site());
+    tokens.extend(quote! { ::evitable::grpc::Code::#code })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(tokens: TokenStream) -> Result<GrpcCode> {
+    let attribute: Attribute = syn::parse_quote!(#[#tokens]);
+    GrpcCode::from_meta(&attribute.meta()?)
+  }
+
+  #[test]
+  fn accepts_error_codes() {
+    assert_eq!(parse(quote!(grpc_code = NotFound)).unwrap().0, "NotFound");
+    assert_eq!(parse(quote!(grpc_code = "Internal")).unwrap().0, "Internal");
+  }
+
+  #[test]
+  fn rejects_ok() {
+    assert!(parse(quote!(grpc_code = Ok)).is_err());
+    assert!(parse(quote!(grpc_code = "Ok")).is_err());
+  }
+}
@@ -0,0 +1,299 @@
+use super::*;
+use proc_macro2::Literal;
+use std::collections::HashMap;
+use syn::Lit;
+
+const REPRS: &[(&str, i128, i128)] = &[
+  ("u8", u8::MIN as i128, u8::MAX as i128),
+  ("u16", u16::MIN as i128, u16::MAX as i128),
+  ("u32", u32::MIN as i128, u32::MAX as i128),
+  ("u64", u64::MIN as i128, u64::MAX as i128),
+  // `usize` and `isize` are limited to the 32-bit range, so the generated
+  // code doesn't depend on the pointer width of the host.
+  ("usize", u32::MIN as i128, u32::MAX as i128),
+  ("i8", i8::MIN as i128, i8::MAX as i128),
+  ("i16", i16::MIN as i128, i16::MAX as i128),
+  ("i32", i32::MIN as i128, i32::MAX as i128),
+  ("i64", i64::MIN as i128, i64::MAX as i128),
+  ("isize", i32::MIN as i128, i32::MAX as i128),
+];
+
+/// The integer type set using `#[evitable(kind_repr = u16)]`.
+#[derive(Clone)]
+pub(crate) struct KindRepr {
+  ty: Ident,
+  min: i128,
+  max: i128,
+}
+
+impl FromMeta for KindRepr {
+  fn from_string<S: Spanned>(value: &str, span: &S) -> Result<Self> {
+    match REPRS.iter().find(|(name, _, _)| *name == value) {
+      Some((name, min, max)) => Ok(KindRepr {
+        ty: Ident::new(name, span.span()),
+        min: *min,
+        max: *max,
+      }),
+      None => Err(Error::unknown_value(value).with_span(span)),
+    }
+  }
+
+  fn from_ident(value: &Ident) -> Result<Self> {
+    let s = value.to_string();
+    Self::from_string(&s, value)
+  }
+}
+
+/// The value set using `#[evitable(discriminant = 42)]`.
+#[derive(Clone, Copy)]
+pub(crate) struct Discriminant {
+  value: i128,
+  span: Span,
+}
+
+impl FromMeta for Discriminant {
+  fn from_lit(value: &Lit) -> Result<Self> {
+    Ok(Discriminant {
+      value: i128::from_lit(value)?,
+      span: value.span(),
+    })
+  }
+}
+
+impl Spanned for Discriminant {
+  fn span(&self) -> Span {
+    self.span
+  }
+}
+
+pub(crate) struct KindReprImpl {
+  repr: Ident,
+  kinds: Vec<(Ident, Discriminant, bool)>,
+  /// Value returned for the hidden `__Nonexhaustive` kind, the largest
+  /// value not used by any other kind.
+  fallback: i128,
+}
+
+pub(crate) fn for_type(
+  data: &ErrorData,
+  ty: &Ident,
+  repr: &Option<KindRepr>,
+) -> Result<Option<KindReprImpl>> {
+  let kinds: Vec<(&Ident, Option<Discriminant>, &Fields<ErrorField>)> = match data {
+    ErrorData::Struct(s) => vec![(ty, s.discriminant, &s.fields)],
+    ErrorData::Enum(variants) => variants
+      .iter()
+      .map(|v| (&v.ident, v.discriminant, &v.fields))
+      .collect(),
+  };
+
+  let repr = match repr {
+    Some(repr) => repr,
+    None => {
+      return match kinds.iter().find_map(|(_, d, _)| *d) {
+        None => Ok(None),
+        Some(d) => {
+          Err(Error::custom("`discriminant` requires `kind_repr` on the context").with_span(&d))
+        }
+      };
+    }
+  };
+
+  let mut seen: HashMap<i128, &Ident> = HashMap::new();
+  let mut result = Vec::with_capacity(kinds.len());
+  for (ident, discriminant, fields) in kinds {
+    let discriminant = match discriminant {
+      Some(d) => d,
+      None => return Err(Error::missing_field("discriminant").with_span(ident)),
+    };
+
+    if discriminant.value < repr.min || discriminant.value > repr.max {
+      return Err(
+        Error::custom(format!(
+          "Discriminant {} is out of range for `{}`",
+          discriminant.value, repr.ty
+        ))
+        .with_span(&discriminant),
+      );
+    }
+
+    if let Some(other) = seen.insert(discriminant.value, ident) {
+      return Err(
+        Error::custom(format!(
+          "Duplicate discriminant {}, also used by `{}`",
+          discriminant.value, other
+        ))
+        .with_span(&discriminant),
+      );
+    }
+
+    let fieldless = !fields.iter().any(|(_, f)| f.include_in_kind);
+    result.push((ident.clone(), discriminant, fieldless));
+  }
+
+  let fallback = (repr.min..=repr.max)
+    .rev()
+    .find(|value| !seen.contains_key(value))
+    .unwrap_or(repr.max);
+
+  Ok(Some(KindReprImpl {
+    repr: repr.ty.clone(),
+    kinds: result,
+    fallback,
+  }))
+}
+
+impl ToTokens for KindReprImpl {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let repr = &self.repr;
+    let arms = self.kinds.iter().map(|(ident, d, _)| {
+      let value = Literal::i128_unsuffixed(d.value);
+      quote! { ErrorKind::#ident { .. } => #value, }
+    });
+    let from_arms = self
+      .kinds
+      .iter()
+      .filter(|(_, _, fieldless)| *fieldless)
+      .map(|(ident, d, _)| {
+        let value = Literal::i128_unsuffixed(d.value);
+        quote! { #value => ::std::result::Result::Ok(ErrorKind::#ident {}), }
+      });
+    let fallback = Literal::i128_unsuffixed(self.fallback);
+
+    tokens.extend(quote! {
+      impl ErrorKind {
+        #[inline]
+        pub fn as_repr(&self) -> #repr {
+          match self {
+            #(#arms)*
+            ErrorKind::__Nonexhaustive => #fallback,
+          }
+        }
+      }
+
+      #[automatically_derived]
+      #[allow(unused_qualifications)]
+      impl ::std::convert::TryFrom<#repr> for ErrorKind {
+        type Error = #repr;
+
+        fn try_from(value: #repr) -> ::std::result::Result<Self, #repr> {
+          match value {
+            #(#from_arms)*
+            _ => ::std::result::Result::Err(value),
+          }
+        }
+      }
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn error(input: DeriveInput) -> String {
+    match ErrorType::from_derive_input(&input) {
+      Ok(_) => panic!("expected an error"),
+      Err(e) => e.to_string(),
+    }
+  }
+
+  #[test]
+  fn duplicate_discriminant() {
+    let message = error(syn::parse_quote! {
+      #[evitable(kind_repr = u8)]
+      enum Context {
+        #[evitable(description = "A", discriminant = 1)]
+        A,
+        #[evitable(description = "B", discriminant = 1)]
+        B,
+      }
+    });
+    assert_eq!(message, "Duplicate discriminant 1, also used by `A`");
+  }
+
+  #[test]
+  fn out_of_range() {
+    let message = error(syn::parse_quote! {
+      #[evitable(kind_repr = u8)]
+      enum Context {
+        #[evitable(description = "A", discriminant = 256)]
+        A,
+      }
+    });
+    assert_eq!(message, "Discriminant 256 is out of range for `u8`");
+
+    let message = error(syn::parse_quote! {
+      #[evitable(description = "A", kind_repr = u64, discriminant = "-1")]
+      struct Context;
+    });
+    assert_eq!(message, "Discriminant -1 is out of range for `u64`");
+
+    let message = error(syn::parse_quote! {
+      #[evitable(description = "A", kind_repr = usize, discriminant = 4294967296)]
+      struct Context;
+    });
+    assert_eq!(
+      message,
+      "Discriminant 4294967296 is out of range for `usize`"
+    );
+  }
+
+  #[test]
+  fn missing_discriminant() {
+    let message = error(syn::parse_quote! {
+      #[evitable(kind_repr = u8)]
+      enum Context {
+        #[evitable(description = "A", discriminant = 1)]
+        A,
+        #[evitable(description = "B")]
+        B,
+      }
+    });
+    assert!(message.contains("discriminant"), "{}", message);
+  }
+
+  #[test]
+  fn discriminant_without_kind_repr() {
+    let message = error(syn::parse_quote! {
+      #[evitable(description = "A", discriminant = 1)]
+      struct Context;
+    });
+    assert_eq!(
+      message,
+      "`discriminant` requires `kind_repr` on the context"
+    );
+  }
+
+  #[test]
+  fn full_u64_range() {
+    let input: DeriveInput = syn::parse_quote! {
+      #[evitable(description = "A", kind_repr = u64, discriminant = 18446744073709551615)]
+      struct Context;
+    };
+    let ty = match ErrorType::from_derive_input(&input) {
+      Ok(ty) => ty,
+      Err(e) => panic!("{}", e),
+    };
+    let kinds = ty.impl_kind_repr.unwrap().kinds;
+    assert_eq!(kinds[0].1.value, u64::MAX as i128);
+  }
+
+  #[test]
+  fn fallback_is_unused() {
+    let input: DeriveInput = syn::parse_quote! {
+      #[evitable(kind_repr = u8)]
+      enum Context {
+        #[evitable(description = "A", discriminant = 255)]
+        A,
+        #[evitable(description = "B", discriminant = 253)]
+        B,
+      }
+    };
+    let ty = match ErrorType::from_derive_input(&input) {
+      Ok(ty) => ty,
+      Err(e) => panic!("{}", e),
+    };
+    assert_eq!(ty.impl_kind_repr.unwrap().fallback, 254);
+  }
+}
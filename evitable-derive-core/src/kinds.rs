@@ -38,6 +38,75 @@ impl<'a> ErrorKind<'a> {
   pub fn is_copy(&self) -> bool {
     self.included_fields.iter().all(|(_, f)| f.is_copy())
   }
+
+  /// Check if any included field is redacted.
+  pub fn is_redacted(&self) -> bool {
+    self.included_fields.iter().any(|(_, f)| f.redact.is_some())
+  }
+
+  /// The included fields, as `(member, binding, field)`.
+  fn members(&self) -> Vec<(TokenStream, Ident, &ErrorField)> {
+    match &self.included_fields {
+      Fields::Unit => Vec::new(),
+      Fields::Named(fields) => fields
+        .iter()
+        .map(|(n, f)| {
+          let binding = Ident::new(&format!("__field_{}", n), Span::call_site());
+          (n.into_token_stream(), binding, *f)
+        })
+        .collect(),
+      Fields::Unnamed(fields) => fields
+        .iter()
+        .map(|(i, f)| {
+          let binding = Ident::new(&format!("__field_{}", i), Span::call_site());
+          (syn::Index::from(*i).into_token_stream(), binding, *f)
+        })
+        .collect(),
+    }
+  }
+
+  /// The included fields, as `member => binding` pairs, followed by
+  /// `= "replacement"` for redacted fields.
+  fn field_bindings(&self) -> TokenStream {
+    let bindings = self
+      .members()
+      .into_iter()
+      .map(|(member, binding, f)| match &f.redact {
+        None => quote! { #member => #binding },
+        Some(Redact(text)) => quote! { #member => #binding = #text },
+      });
+
+    quote! { { #(#bindings),* } }
+  }
+
+  /// A `Debug` match arm for the kind, showing redacted fields using
+  /// their replacement text.
+  fn debug_arm(&self, name: &Ident) -> TokenStream {
+    let members = self.members();
+    let name_str = name.to_string();
+    let patterns = members
+      .iter()
+      .map(|(member, binding, _)| quote! { #member: #binding });
+    let values: Vec<_> = members
+      .iter()
+      .map(|(_, binding, f)| match &f.redact {
+        None => quote! { #binding },
+        Some(Redact(text)) => quote! { &::evitable::Redacted::__new(#binding, #text) },
+      })
+      .collect();
+    let debug = match &self.included_fields {
+      Fields::Named(_) if !members.is_empty() => {
+        let names = members.iter().map(|(member, _, _)| member.to_string());
+        quote! { f.debug_struct(#name_str)#(.field(#names, #values))*.finish() }
+      }
+      Fields::Unnamed(_) if !members.is_empty() => {
+        quote! { f.debug_tuple(#name_str)#(.field(#values))*.finish() }
+      }
+      _ => quote! { f.write_str(#name_str) },
+    };
+
+    quote! { ErrorKind::#name { #(#patterns,)* .. } => #debug, }
+  }
 }
 
 impl<'a> ToTokens for ErrorKind<'a> {
@@ -99,6 +168,9 @@ impl<'a> ErrorKinds<'a> {
 impl<'a> ToTokens for ErrorKinds<'a> {
   fn to_tokens(&self, tokens: &mut TokenStream) {
     let mut kinds = Vec::new();
+    let mut fields = Vec::new();
+    let mut debug_arms = Vec::new();
+    let mut redacted = false;
     let mut copy = true;
     let vis = self.vis();
     match self {
@@ -110,6 +182,10 @@ impl<'a> ToTokens for ErrorKinds<'a> {
         kinds.push(quote! {
           #n #k
         });
+        let bindings = k.field_bindings();
+        fields.push(quote! { #n #bindings });
+        debug_arms.push(k.debug_arm(n));
+        redacted |= k.is_redacted();
       }
 
       ErrorKinds::Enum(_, _, variants) => {
@@ -121,6 +197,10 @@ impl<'a> ToTokens for ErrorKinds<'a> {
           kinds.push(quote! {
             #n #k
           });
+          let bindings = k.field_bindings();
+          fields.push(quote! { #n #bindings });
+          debug_arms.push(k.debug_arm(n));
+          redacted |= k.is_redacted();
         }
       }
     };
@@ -130,8 +210,27 @@ impl<'a> ToTokens for ErrorKinds<'a> {
     } else {
       TokenStream::new()
     };
+    // Kinds with redacted fields get a `Debug` impl hiding them.
+    let (derive_debug, impl_debug) = if redacted {
+      let impl_debug = quote! {
+        #[automatically_derived]
+        #[allow(unused_qualifications)]
+        impl ::std::fmt::Debug for ErrorKind {
+          fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+              #(#debug_arms)*
+              ErrorKind::__Nonexhaustive => f.write_str("__Nonexhaustive"),
+            }
+          }
+        }
+      };
+      (TokenStream::new(), impl_debug)
+    } else {
+      (quote! { , Debug }, TokenStream::new())
+    };
+
     tokens.extend(quote! {
-      #[derive(PartialEq, Debug, Clone #copy)]
+      #[derive(PartialEq, Clone #derive_debug #copy)]
       #vis enum ErrorKind {
         #(#kinds,)*
 
@@ -149,9 +248,9 @@ impl<'a> ToTokens for ErrorKinds<'a> {
         }
       }
 
-      #[automatically_derived]
-      #[allow(unused_qualifications)]
-      impl ::evitable::EvitableErrorKind for ErrorKind {}
+      #impl_debug
+
+      ::evitable::__impl_kind_fields!(ErrorKind { #(#fields),* });
     });
   }
 }
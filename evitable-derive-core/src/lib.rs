@@ -14,6 +14,7 @@ use evitable_syn_meta_ext::{
   MapFields, Meta, NestedMeta, PathExt,
 };
 use from::FromImpl;
+use from_evitable::FromEvitableImpl;
 use ident_case::RenameRule;
 use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
@@ -24,16 +25,25 @@ use syn::{
   Generics, Ident, Path, Type, Variant, Visibility,
 };
 use trait_assert::assert_trait_impl;
+use variant_attrs::VariantAttrs;
+pub use with_context::with_context;
 
 mod attrs;
 mod description;
 mod display;
 mod from;
+mod from_any;
 mod from_context;
+mod from_evitable;
+mod grpc_code;
 mod impl_display;
+mod kind_repr;
 mod kinds;
+mod severity;
 mod trait_assert;
+mod variant_attrs;
 mod visibility;
+mod with_context;
 
 trait IntoIdent<'a> {
   fn into_ident(self) -> Cow<'a, Ident>;
@@ -135,10 +145,26 @@ impl FromMeta for CopyMethod {
   }
 }
 
+/// Replacement text of a field marked with `#[evitable(redact)]`.
+#[derive(Clone)]
+struct Redact(String);
+
+impl FromMeta for Redact {
+  fn from_empty() -> Result<Self> {
+    Ok(Redact("<redacted>".to_owned()))
+  }
+
+  fn from_string<S: Spanned>(value: &str, _span: &S) -> Result<Self> {
+    Ok(Redact(value.to_owned()))
+  }
+}
+
 struct ErrorField {
   ty: Type,
   include_in_kind: bool,
   method: CopyMethod,
+  retry_after: bool,
+  redact: Option<Redact>,
 }
 
 impl ErrorField {
@@ -151,7 +177,12 @@ impl ErrorField {
 struct ErrorVariant {
   ident: Ident,
   description: ResolvedDescription,
+  user_message: Option<ResolvedDescription>,
   from_impls: Vec<FromImpl>,
+  from_any: bool,
+  attrs: VariantAttrs,
+  retry_after: Option<TokenStream>,
+  discriminant: Option<kind_repr::Discriminant>,
   fields: Fields<ErrorField>,
 }
 
@@ -173,7 +204,11 @@ impl ErrorVariant {
 
 struct ErrorStruct {
   description: ResolvedDescription,
+  user_message: Option<ResolvedDescription>,
   from_impls: Vec<FromImpl>,
+  from_any: bool,
+  retry_after: Option<TokenStream>,
+  discriminant: Option<kind_repr::Discriminant>,
   fields: Fields<ErrorField>,
 }
 
@@ -230,6 +265,34 @@ impl FromMeta for TypeAliasName {
   }
 }
 
+/// The message included in the HTTP responses of the generated error,
+/// set using `#[evitable(http_message = "user")]`.
+#[derive(Clone, Copy, Default)]
+enum HttpMessage {
+  #[default]
+  Description,
+  User,
+}
+
+impl FromMeta for HttpMessage {
+  fn from_string<S: Spanned>(value: &str, span: &S) -> Result<Self> {
+    match value {
+      "description" => Ok(HttpMessage::Description),
+      "user" => Ok(HttpMessage::User),
+      _ => Err(Error::unknown_value(value).with_span(span)),
+    }
+  }
+}
+
+impl ToTokens for HttpMessage {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    tokens.extend(match self {
+      HttpMessage::Description => quote! { ::evitable::Message::Description },
+      HttpMessage::User => quote! { ::evitable::Message::User },
+    })
+  }
+}
+
 struct ErrorType {
   ident: Ident,
   prefix: Option<String>,
@@ -240,12 +303,21 @@ struct ErrorType {
   mod_name: Ident,
   mod_vis: Visibility,
   impls_from: Vec<from::FromImplFor>,
+  impls_from_evitable: Vec<from_evitable::FromEvitableImplFor>,
+  impl_from_any: Option<from_any::FromAnyImplFor>,
+  impl_kind_repr: Option<kind_repr::KindReprImpl>,
 }
 
 struct ErrorTypeAttrs {
   error_type_name: TypeAliasName,
   result_type_name: TypeAliasName,
   kind_type_name: TypeAliasName,
+  from_evitable: Vec<FromEvitableImpl>,
+  kind_repr: Option<kind_repr::KindRepr>,
+  http_message: HttpMessage,
+  variant_attrs: VariantAttrs,
+  /// Fallback user message of enum variants without one.
+  user_message: Option<ResolvedDescription>,
 }
 
 impl ErrorTypeAttrs {
@@ -253,11 +325,20 @@ impl ErrorTypeAttrs {
     let error_type_name = attrs.get_optional("error_type")?.unwrap_or_default();
     let result_type_name = attrs.get_optional("result_type")?.unwrap_or_default();
     let kind_type_name = attrs.get_optional("kind_type")?.unwrap_or_default();
+    let from_evitable = attrs.get_all("from_evitable")?;
+    let kind_repr = attrs.get_optional("kind_repr")?;
+    let http_message = attrs.get_optional("http_message")?.unwrap_or_default();
+    let variant_attrs = VariantAttrs::from_attrs(attrs)?;
 
     Ok(Self {
       error_type_name,
       result_type_name,
       kind_type_name,
+      from_evitable,
+      kind_repr,
+      http_message,
+      variant_attrs,
+      user_message: None,
     })
   }
 }
@@ -272,13 +353,26 @@ impl FromVariant for ErrorVariant {
 
     let description: Description = attrs.get_required("description", &variant.ident)?;
     let description = description.resolve_from_variant(&fields)?;
+    let user_message: Option<Description> = attrs.get_optional("user_message")?;
+    let user_message = user_message
+      .map(|m| m.resolve_from_variant(&fields))
+      .transpose()?;
     let from_impls = attrs.get_list("from")?;
+    let from_any = attrs.get_optional("from_any")?.unwrap_or(false);
+    let variant_attrs = VariantAttrs::from_attrs(&mut attrs)?;
+    let retry_after = variant_attrs::retry_after_field(&fields, &variant.ident)?;
+    let discriminant = attrs.get_optional("discriminant")?;
     attrs.ensure_used()?;
 
     Ok(ErrorVariant {
       ident: variant.ident.clone(),
       description,
+      user_message,
       from_impls,
+      from_any,
+      attrs: variant_attrs,
+      retry_after,
+      discriminant,
       fields,
     })
   }
@@ -301,11 +395,15 @@ impl FromField for ErrorField {
       Default::default()
     };
 
+    let retry_after = attrs.get_optional("retry_after")?.unwrap_or(false);
+    let redact = attrs.get_optional("redact")?;
     attrs.ensure_used()?;
     Ok(ErrorField {
       ty: field.ty.clone(),
       include_in_kind,
       method,
+      retry_after,
+      redact,
     })
   }
 }
@@ -355,6 +453,13 @@ impl ErrorType {
     let mod_name = Ident::new(&mod_name, ident.span());
     let mod_vis = visibility::inherited(&vis, 1);
     let impls_from = Self::impl_froms(&data, &ident, &mod_name)?;
+    let impls_from_evitable = attrs
+      .from_evitable
+      .iter()
+      .map(|f| f.for_type(&mod_name))
+      .collect();
+    let impl_from_any = from_any::for_type(&data, &mod_name, &ident)?;
+    let impl_kind_repr = kind_repr::for_type(&data, &ident, &attrs.kind_repr)?;
 
     Ok(Self {
       ident,
@@ -366,6 +471,9 @@ impl ErrorType {
       mod_name,
       mod_vis,
       impls_from,
+      impls_from_evitable,
+      impl_from_any,
+      impl_kind_repr,
     })
   }
 }
@@ -385,7 +493,11 @@ impl FromDeriveInput for ErrorType {
       .collect();
 
     let mut evitable_attrs = Attrs::from_attributes(attrs)?;
-    let attrs = ErrorTypeAttrs::from_attrs(&mut evitable_attrs)?;
+    let mut attrs = ErrorTypeAttrs::from_attrs(&mut evitable_attrs)?;
+    let user_message: Option<Description> = evitable_attrs.get_optional("user_message")?;
+    attrs.user_message = user_message
+      .map(|m| m.resolve_from_variant(&Fields::Unit))
+      .transpose()?;
     evitable_attrs.ensure_used()?;
     let data = ErrorData::Enum(variants?);
 
@@ -404,12 +516,23 @@ impl FromDeriveInput for ErrorType {
     let attrs = ErrorTypeAttrs::from_attrs(&mut evitable_attrs)?;
     let description: Description = evitable_attrs.get_required("description", ident)?;
     let description = description.resolve_from_inst(&fields, "self")?;
+    let user_message: Option<Description> = evitable_attrs.get_optional("user_message")?;
+    let user_message = user_message
+      .map(|m| m.resolve_from_inst(&fields, "self"))
+      .transpose()?;
     let from_impls = evitable_attrs.get_list("from")?;
+    let from_any = evitable_attrs.get_optional("from_any")?.unwrap_or(false);
+    let retry_after = variant_attrs::retry_after_field(&fields, ident)?;
+    let discriminant = evitable_attrs.get_optional("discriminant")?;
     evitable_attrs.ensure_used()?;
     let data = ErrorData::Struct(ErrorStruct {
       description,
+      user_message,
       fields,
       from_impls,
+      from_any,
+      retry_after,
+      discriminant,
     });
 
     ErrorType::new(ident.clone(), vis.clone(), generics.clone(), data, attrs)
@@ -427,6 +550,14 @@ impl ToTokens for ErrorType {
     let from_context = from_context::for_type(&kinds, ty);
     let impl_display = DisplayImpl::for_type(self);
     let impls_from = &self.impls_from;
+    let impls_from_evitable = &self.impls_from_evitable;
+    let impl_from_any = &self.impl_from_any;
+    let impl_kind_repr = &self.impl_kind_repr;
+    let context_methods = variant_attrs::context_methods(self);
+    let unredacted_method = impl_display.unredacted_method();
+    let user_message_method = impl_display.user_message_method();
+    let kind_methods = variant_attrs::kind_methods(self);
+    let http_message = self.attrs.http_message;
 
     tokens.extend(quote! {
       #vis mod #mod_name {
@@ -434,10 +565,21 @@ impl ToTokens for ErrorType {
 
         #kinds
         #from_context
+        #impl_kind_repr
+
+        #[automatically_derived]
+        #[allow(unused_qualifications)]
+        impl ::evitable::EvitableErrorKind for ErrorKind {
+          #kind_methods
+        }
 
         #mod_item_vis struct Error {
           context: super::#ty,
           backtrace: ::evitable::Backtrace,
+          location: &'static ::std::panic::Location<'static>,
+          span_trace: ::evitable::SpanTrace,
+          attachments: ::evitable::Attachments,
+          frames: ::evitable::Frames,
           source: Option<Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync + 'static>>,
         }
 
@@ -452,6 +594,11 @@ impl ToTokens for ErrorType {
             &self.backtrace
           }
 
+          #[inline]
+          fn location(&self) -> &'static ::std::panic::Location<'static> {
+            self.location
+          }
+
           #[inline]
           fn kind(&self) -> ErrorKind {
             ::evitable::ErrorContext::kind(&self.context)
@@ -462,6 +609,7 @@ impl ToTokens for ErrorType {
         #[allow(unused_qualifications)]
         impl ::std::convert::From<super::#ty> for Error {
           #[inline]
+          #[track_caller]
           fn from(context: super::#ty) -> Self {
             <Error as ::evitable::EvitableError>::new(context, None)
           }
@@ -472,6 +620,9 @@ impl ToTokens for ErrorType {
         impl ::std::fmt::Display for Error {
           fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
             ::std::fmt::Display::fmt(&self.context, f)?;
+            for frame in &self.frames {
+              write!(f, "\n  {}", frame)?;
+            }
             if let Some(source) = &self.source {
               f.write_str("\n---- source ----\n")?;
               ::std::fmt::Display::fmt(source, f)?;
@@ -487,6 +638,13 @@ impl ToTokens for ErrorType {
           fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
             // TODO: Include backtrace
             ::std::fmt::Debug::fmt(&self.context, f)?;
+            write!(f, "\n  at {}", self.location)?;
+            for frame in &self.frames {
+              write!(f, "\n  {:?}", frame)?;
+            }
+            for attachment in self.attachments.printable() {
+              write!(f, "\n  - {}", attachment)?;
+            }
             if let Some(source) = &self.source {
               f.write_str("\n---- source ----\n")?;
               ::std::fmt::Debug::fmt(source, f)?;
@@ -530,18 +688,73 @@ impl ToTokens for ErrorType {
           }
 
           #[inline]
-          fn new(context: Self::Context, source: Option<Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync + 'static>>) -> Self {
+          fn location(&self) -> &'static ::std::panic::Location<'static> {
+            Error::location(self)
+          }
+
+          #[inline]
+          fn span_trace(&self) -> &::evitable::SpanTrace {
+            &self.span_trace
+          }
+
+          #[inline]
+          fn attachment_store(&self) -> &::evitable::Attachments {
+            &self.attachments
+          }
+
+          #[inline]
+          fn attachment_store_mut(&mut self) -> &mut ::evitable::Attachments {
+            &mut self.attachments
+          }
+
+          #[inline]
+          fn frames(&self) -> &::evitable::Frames {
+            &self.frames
+          }
+
+          #[inline]
+          fn frames_mut(&mut self) -> &mut ::evitable::Frames {
+            &mut self.frames
+          }
+
+          fn new_at(
+            context: Self::Context,
+            source: Option<Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync + 'static>>,
+            location: &'static ::std::panic::Location<'static>,
+          ) -> Self {
             let backtrace = ::evitable::Backtrace::new();
 
-            Self {
+            let error = Self {
               context,
               source,
               backtrace,
-            }
+              location,
+              span_trace: ::evitable::SpanTrace::capture(),
+              attachments: ::evitable::Attachments::new(),
+              frames: ::evitable::Frames::new(),
+            };
+
+            ::evitable::__run_hook(&error);
+            error
           }
         }
 
         #(#impls_from)*
+        #(#impls_from_evitable)*
+        #impl_from_any
+
+        #[automatically_derived]
+        #[allow(unused_qualifications)]
+        impl ::std::convert::From<Error> for ::std::io::Error {
+          #[inline]
+          fn from(error: Error) -> Self {
+            let kind = ::evitable::EvitableError::io_kind(&error);
+            ::std::io::Error::new(kind, error)
+          }
+        }
+
+        ::evitable::__impl_web!(Error, #http_message);
+        ::evitable::__impl_tonic!(Error);
 
         #mod_item_vis type Result<T> = ::std::result::Result<T, Error>;
       }
@@ -557,6 +770,10 @@ impl ToTokens for ErrorType {
         fn kind(&self) -> Self::Kind {
           #mod_name::ErrorKind::from_context(self)
         }
+
+        #context_methods
+        #unredacted_method
+        #user_message_method
       }
     });
 
@@ -0,0 +1,41 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Severity {
+  Trace,
+  Debug,
+  Info,
+  Warn,
+  #[default]
+  Error,
+}
+
+impl FromMeta for Severity {
+  fn from_string<S: Spanned>(value: &str, span: &S) -> Result<Self> {
+    match value {
+      "trace" => Ok(Severity::Trace),
+      "debug" => Ok(Severity::Debug),
+      "info" => Ok(Severity::Info),
+      "warn" => Ok(Severity::Warn),
+      "error" => Ok(Severity::Error),
+      v => Err(Error::unknown_value(v).with_span(span)),
+    }
+  }
+
+  fn from_ident(value: &Ident) -> Result<Self> {
+    let s = value.to_string();
+    Self::from_string(&s, value)
+  }
+}
+
+impl ToTokens for Severity {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    tokens.extend(match self {
+      Severity::Trace => quote! { ::evitable::Severity::Trace },
+      Severity::Debug => quote! { ::evitable::Severity::Debug },
+      Severity::Info => quote! { ::evitable::Severity::Info },
+      Severity::Warn => quote! { ::evitable::Severity::Warn },
+      Severity::Error => quote! { ::evitable::Severity::Error },
+    })
+  }
+}
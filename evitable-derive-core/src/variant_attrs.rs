@@ -0,0 +1,401 @@
+use super::*;
+use grpc_code::GrpcCode;
+use severity::Severity;
+use syn::Lit;
+
+#[derive(Clone, Copy)]
+pub(crate) struct HttpStatus(u16);
+
+/// A process exit code. `0` is reserved for success.
+#[derive(Clone, Copy)]
+pub(crate) struct ExitCode(u8);
+
+impl FromMeta for ExitCode {
+  fn from_lit(value: &Lit) -> Result<Self> {
+    match u8::from_lit(value)? {
+      0 => Err(Error::unknown_value("0").with_span(value)),
+      code => Ok(ExitCode(code)),
+    }
+  }
+}
+
+/// A numeric error code for the C ABI. `0` is reserved for success, and
+/// `-1` for panics.
+#[derive(Clone, Copy)]
+pub(crate) struct FfiCode(i32);
+
+impl FromMeta for FfiCode {
+  fn from_lit(value: &Lit) -> Result<Self> {
+    match i32::from_lit(value)? {
+      code @ (0 | -1) => Err(Error::unknown_value(&code.to_string()).with_span(value)),
+      code => Ok(FfiCode(code)),
+    }
+  }
+}
+
+/// A variant of `std::io::ErrorKind`. The name is checked by the
+/// compiler, in the generated code.
+#[derive(Clone)]
+pub(crate) struct IoKind(Ident);
+
+impl FromMeta for IoKind {
+  fn from_string<S: Spanned>(value: &str, span: &S) -> Result<Self> {
+    match syn::parse_str::<Ident>(value) {
+      Ok(ident) => Ok(IoKind(Ident::new(&ident.to_string(), span.span()))),
+      Err(_) => Err(Error::unknown_value(value).with_span(span)),
+    }
+  }
+
+  fn from_ident(value: &Ident) -> Result<Self> {
+    Ok(IoKind(value.clone()))
+  }
+}
+
+impl FromMeta for HttpStatus {
+  fn from_lit(value: &Lit) -> Result<Self> {
+    let status = u16::from_lit(value)?;
+    if (100..600).contains(&status) {
+      Ok(HttpStatus(status))
+    } else {
+      Err(Error::unknown_value(&status.to_string()).with_span(value))
+    }
+  }
+}
+
+/// Attributes classifying an error, which can be set on the context type
+/// (as a default for all variants) and on individual variants.
+#[derive(Clone, Default)]
+pub(crate) struct VariantAttrs {
+  severity: Option<Severity>,
+  code: Option<String>,
+  retryable: Option<bool>,
+  http_status: Option<HttpStatus>,
+  grpc_code: Option<GrpcCode>,
+  exit_code: Option<ExitCode>,
+  io_kind: Option<IoKind>,
+  ffi_code: Option<FfiCode>,
+}
+
+impl VariantAttrs {
+  pub fn from_attrs(attrs: &mut Attrs) -> Result<Self> {
+    let severity = attrs.get_optional("severity")?;
+    let code = attrs.get_optional("code")?;
+    let retryable = attrs.get_optional("retryable")?;
+    let http_status = attrs.get_optional("http_status")?;
+    let grpc_code = attrs.get_optional("grpc_code")?;
+    let exit_code = attrs.get_optional("exit_code")?;
+    let io_kind = attrs.get_optional("io_kind")?;
+    let ffi_code = attrs.get_optional("ffi_code")?;
+
+    Ok(Self {
+      severity,
+      code,
+      retryable,
+      http_status,
+      grpc_code,
+      exit_code,
+      io_kind,
+      ffi_code,
+    })
+  }
+
+  fn or(&self, defaults: &VariantAttrs) -> VariantAttrs {
+    VariantAttrs {
+      severity: self.severity.or(defaults.severity),
+      code: self.code.clone().or_else(|| defaults.code.clone()),
+      retryable: self.retryable.or(defaults.retryable),
+      http_status: self.http_status.or(defaults.http_status),
+      grpc_code: self.grpc_code.or(defaults.grpc_code),
+      exit_code: self.exit_code.or(defaults.exit_code),
+      io_kind: self.io_kind.clone().or_else(|| defaults.io_kind.clone()),
+      ffi_code: self.ffi_code.or(defaults.ffi_code),
+    }
+  }
+
+  fn severity(&self) -> TokenStream {
+    self.severity.unwrap_or_default().into_token_stream()
+  }
+
+  fn code(&self) -> TokenStream {
+    match &self.code {
+      None => quote! { ::std::option::Option::None },
+      Some(code) => quote! { ::std::option::Option::Some(#code) },
+    }
+  }
+
+  fn is_retryable(&self) -> TokenStream {
+    let retryable = self.retryable.unwrap_or(false);
+    quote! { #retryable }
+  }
+
+  fn http_status(&self) -> TokenStream {
+    let HttpStatus(status) = self.http_status.unwrap_or(HttpStatus(500));
+    quote! { #status }
+  }
+
+  fn grpc_code(&self) -> TokenStream {
+    self.grpc_code.unwrap_or_default().into_token_stream()
+  }
+
+  fn exit_code(&self) -> TokenStream {
+    let ExitCode(code) = self.exit_code.unwrap_or(ExitCode(1));
+    quote! { #code }
+  }
+
+  fn io_kind(&self) -> TokenStream {
+    match &self.io_kind {
+      None => quote! { ::std::io::ErrorKind::Other },
+      Some(IoKind(kind)) => quote! { ::std::io::ErrorKind::#kind },
+    }
+  }
+
+  fn ffi_code(&self) -> TokenStream {
+    let FfiCode(code) = self.ffi_code.unwrap_or(FfiCode(1));
+    quote! { #code }
+  }
+}
+
+struct Method {
+  name: Ident,
+  ret: TokenStream,
+  value: fn(&VariantAttrs) -> TokenStream,
+}
+
+fn methods() -> Vec<Method> {
+  vec![
+    Method {
+      name: Ident::new("severity", Span::call_site()),
+      ret: quote! { ::evitable::Severity },
+      value: VariantAttrs::severity,
+    },
+    Method {
+      name: Ident::new("code", Span::call_site()),
+      ret: quote! { ::std::option::Option<&'static str> },
+      value: VariantAttrs::code,
+    },
+    Method {
+      name: Ident::new("is_retryable", Span::call_site()),
+      ret: quote! { bool },
+      value: VariantAttrs::is_retryable,
+    },
+    Method {
+      name: Ident::new("http_status", Span::call_site()),
+      ret: quote! { u16 },
+      value: VariantAttrs::http_status,
+    },
+    Method {
+      name: Ident::new("grpc_code", Span::call_site()),
+      ret: quote! { ::evitable::grpc::Code },
+      value: VariantAttrs::grpc_code,
+    },
+    Method {
+      name: Ident::new("exit_code", Span::call_site()),
+      ret: quote! { u8 },
+      value: VariantAttrs::exit_code,
+    },
+    Method {
+      name: Ident::new("io_kind", Span::call_site()),
+      ret: quote! { ::std::io::ErrorKind },
+      value: VariantAttrs::io_kind,
+    },
+    Method {
+      name: Ident::new("ffi_code", Span::call_site()),
+      ret: quote! { i32 },
+      value: VariantAttrs::ffi_code,
+    },
+  ]
+}
+
+/// Methods of [ErrorContext](evitable::ErrorContext) derived from the
+/// variant attributes.
+pub(crate) fn context_methods(error_type: &ErrorType) -> TokenStream {
+  let defaults = &error_type.attrs.variant_attrs;
+  let ty = &error_type.ident;
+  let mut tokens = TokenStream::new();
+  for Method { name, ret, value } in methods() {
+    let body = match &error_type.data {
+      ErrorData::Struct(_) => value(defaults),
+      ErrorData::Enum(variants) => {
+        let arms = variants.iter().map(|v| {
+          let ident = &v.ident;
+          let value = value(&v.attrs.or(defaults));
+          quote! { #ty::#ident { .. } => #value, }
+        });
+
+        quote! { match self { #(#arms)* } }
+      }
+    };
+
+    tokens.extend(quote! {
+      #[inline]
+      fn #name(&self) -> #ret {
+        #body
+      }
+    });
+  }
+
+  tokens.extend(retry_after_method(error_type));
+  tokens
+}
+
+/// Get the field marked with `#[evitable(retry_after)]`, if any.
+pub(crate) fn retry_after_field<S: Spanned>(
+  fields: &Fields<ErrorField>,
+  span: &S,
+) -> Result<Option<TokenStream>> {
+  let mut marked: Vec<TokenStream> = match fields {
+    Fields::Unit => Vec::new(),
+    Fields::Named(f) => f
+      .iter()
+      .filter(|(_, f)| f.retry_after)
+      .map(|(n, _)| n.into_token_stream())
+      .collect(),
+    Fields::Unnamed(f) => f
+      .iter()
+      .filter(|(_, f)| f.retry_after)
+      .map(|(i, _)| syn::Index::from(*i).into_token_stream())
+      .collect(),
+  };
+
+  match marked.len() {
+    0 | 1 => Ok(marked.pop()),
+    _ => Err(Error::duplicate_field("retry_after").with_span(span)),
+  }
+}
+
+fn retry_after_method(error_type: &ErrorType) -> TokenStream {
+  let ty = &error_type.ident;
+  let arm = |path: TokenStream, member: &Option<TokenStream>| match member {
+    None => quote! { #path { .. } => ::std::option::Option::None, },
+    Some(member) => quote! {
+      #path { #member: retry_after, .. } => ::evitable::retry::RetryAfter::retry_after(retry_after),
+    },
+  };
+
+  let arms = match &error_type.data {
+    ErrorData::Struct(s) => arm(quote! { #ty }, &s.retry_after),
+    ErrorData::Enum(variants) => variants
+      .iter()
+      .map(|v| {
+        let ident = &v.ident;
+        arm(quote! { #ty::#ident }, &v.retry_after)
+      })
+      .collect(),
+  };
+
+  quote! {
+    #[inline]
+    fn retry_after(&self) -> ::std::option::Option<::std::time::Duration> {
+      match self {
+        #arms
+      }
+    }
+  }
+}
+
+/// Methods of [EvitableErrorKind](evitable::EvitableErrorKind) derived
+/// from the variant attributes.
+pub(crate) fn kind_methods(error_type: &ErrorType) -> TokenStream {
+  let defaults = &error_type.attrs.variant_attrs;
+  let mut tokens = TokenStream::new();
+  for Method { name, ret, value } in methods() {
+    let arms = match &error_type.data {
+      ErrorData::Struct(_) => {
+        let ident = &error_type.ident;
+        let value = value(defaults);
+        quote! { ErrorKind::#ident { .. } => #value, }
+      }
+      ErrorData::Enum(variants) => {
+        let arms = variants.iter().map(|v| {
+          let ident = &v.ident;
+          let value = value(&v.attrs.or(defaults));
+          quote! { ErrorKind::#ident { .. } => #value, }
+        });
+
+        quote! { #(#arms)* }
+      }
+    };
+
+    let default = value(defaults);
+    tokens.extend(quote! {
+      #[inline]
+      fn #name(&self) -> #ret {
+        match self {
+          #arms
+          ErrorKind::__Nonexhaustive => #default,
+        }
+      }
+    });
+  }
+
+  let kinds: Vec<(&Ident, &Fields<ErrorField>)> = match &error_type.data {
+    ErrorData::Struct(s) => vec![(&error_type.ident, &s.fields)],
+    ErrorData::Enum(variants) => variants.iter().map(|v| (&v.ident, &v.fields)).collect(),
+  };
+  let idents = kinds.iter().map(|(i, _)| i);
+  let names = kinds.iter().map(|(i, _)| i.to_string());
+  tokens.extend(quote! {
+    #[inline]
+    fn name(&self) -> &'static str {
+      match self {
+        #(ErrorKind::#idents { .. } => #names,)*
+        ErrorKind::__Nonexhaustive => "__Nonexhaustive",
+      }
+    }
+  });
+
+  // Only kinds without fields can be recreated from their name.
+  let fieldless: Vec<_> = kinds
+    .iter()
+    .filter(|(_, fields)| !fields.iter().any(|(_, f)| f.include_in_kind))
+    .map(|(i, _)| *i)
+    .collect();
+  let names = fieldless.iter().map(|i| i.to_string());
+  tokens.extend(quote! {
+    fn from_name(name: &str) -> ::std::option::Option<Self> {
+      match name {
+        #(#names => ::std::option::Option::Some(ErrorKind::#fieldless {}),)*
+        _ => ::std::option::Option::None,
+      }
+    }
+  });
+
+  tokens.extend(quote! {
+    #[inline]
+    fn from_error(
+      error: &(dyn ::std::error::Error + 'static),
+    ) -> ::std::option::Option<Self> {
+      error
+        .downcast_ref::<Error>()
+        .map(::evitable::EvitableError::kind)
+    }
+  });
+
+  tokens
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse<T: FromMeta>(tokens: TokenStream) -> Result<T> {
+    let attribute: Attribute = syn::parse_quote!(#[#tokens]);
+    T::from_meta(&attribute.meta()?)
+  }
+
+  #[test]
+  fn exit_code() {
+    assert_eq!(parse::<ExitCode>(quote!(exit_code = 3)).unwrap().0, 3);
+    assert!(parse::<ExitCode>(quote!(exit_code = 0)).is_err());
+  }
+
+  #[test]
+  fn ffi_code() {
+    assert_eq!(parse::<FfiCode>(quote!(ffi_code = 2)).unwrap().0, 2);
+    assert_eq!(parse::<FfiCode>(quote!(ffi_code = "-2")).unwrap().0, -2);
+    assert_eq!(parse::<FfiCode>(quote!(ffi_code = -3)).unwrap().0, -3);
+    assert!(parse::<FfiCode>(quote!(ffi_code = -1)).is_err());
+    assert!(parse::<FfiCode>(quote!(ffi_code = 0)).is_err());
+    assert!(parse::<FfiCode>(quote!(ffi_code = "-1")).is_err());
+  }
+}
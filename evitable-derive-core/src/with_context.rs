@@ -0,0 +1,123 @@
+use super::*;
+use syn::parse::{ParseStream, Parser};
+use syn::visit_mut::{self, VisitMut};
+use syn::{parse_quote, Expr, Item, ItemFn, ReturnType, Token};
+
+/// Wraps the body of a function, so that any error returned from it is
+/// converted into the given context (with the original error as source).
+/// The body is evaluated in a closure (or async block for async functions)
+/// with the declared return type of the function, so `return Err(..)`,
+/// `ensure!` and `fail!` behave like they do in an unannotated function.
+/// To allow `?` to be used with any error type, every `?` in the body
+/// (outside of closures, async blocks, nested items and macros) is
+/// rewritten to wrap the error in the context right away. The context
+/// expression is only evaluated in the error case. Prefixed with `move`,
+/// it is evaluated before the body instead, so it can refer to parameters
+/// the body takes ownership of.
+pub fn with_context(meta: &TokenStream, item: &ItemFn) -> TokenStream {
+  let parser = |input: ParseStream| {
+    let eager = input.parse::<Option<Token![move]>>()?.is_some();
+    let context: Expr = input.parse()?;
+    Ok((eager, context))
+  };
+
+  let (eager, context) = match parser.parse2(meta.clone()) {
+    Ok(r) => r,
+    Err(err) => return err.to_compile_error(),
+  };
+
+  let attrs = &item.attrs;
+  let vis = &item.vis;
+  let sig = &item.sig;
+  let ret = match &sig.output {
+    ReturnType::Type(_, ty) => ty,
+    ReturnType::Default => {
+      return syn::Error::new_spanned(
+        &sig.ident,
+        "with_context requires a function returning a Result",
+      )
+      .to_compile_error()
+    }
+  };
+
+  let result = Ident::new("__evitable_result", Span::call_site());
+  let wrapped = Ident::new("__evitable_wrapped", Span::call_site());
+  let (capture, context) = if eager {
+    let ident = Ident::new("__evitable_context", Span::call_site());
+    (
+      quote! { let mut #ident = ::std::option::Option::Some(#context); },
+      quote! { ::std::option::Option::take(&mut #ident).unwrap() },
+    )
+  } else {
+    (TokenStream::new(), quote! { #context })
+  };
+
+  let mut block = item.block.clone();
+  TryRewriter {
+    context: &context,
+    wrapped: &wrapped,
+  }
+  .visit_block_mut(&mut block);
+
+  let eval = if sig.asyncness.is_some() {
+    quote! {
+      let #result: #ret = async #block.await;
+    }
+  } else {
+    quote! {
+      #[allow(clippy::redundant_closure_call)]
+      let #result = (|| -> #ret #block)();
+    }
+  };
+
+  quote! {
+    #(#attrs)*
+    #vis #sig {
+      #capture
+      #[allow(unused_mut)]
+      let mut #wrapped = false;
+      #eval
+      match #result {
+        ::std::result::Result::Err(error) if !#wrapped => ::std::result::Result::Err(
+          ::std::convert::From::from(::evitable::ErrorContext::into_error(#context, error)),
+        ),
+        result => result,
+      }
+    }
+  }
+}
+
+/// Rewrites `expr?` into a match that wraps the error in the context and
+/// marks it as wrapped, so it is not wrapped again when leaving the body.
+struct TryRewriter<'a> {
+  context: &'a TokenStream,
+  wrapped: &'a Ident,
+}
+
+impl<'a> VisitMut for TryRewriter<'a> {
+  fn visit_expr_mut(&mut self, expr: &mut Expr) {
+    match expr {
+      Expr::Closure(_) | Expr::Async(_) | Expr::Macro(_) => return,
+      _ => visit_mut::visit_expr_mut(self, expr),
+    }
+
+    if let Expr::Try(t) = expr {
+      let inner = &t.expr;
+      let context = self.context;
+      let wrapped = self.wrapped;
+      *expr = parse_quote! {
+        match #inner {
+          ::std::result::Result::Ok(value) => value,
+          ::std::result::Result::Err(error) => {
+            #wrapped = true;
+            return ::std::result::Result::Err(::std::convert::From::from(
+              ::evitable::ErrorContext::into_error(#context, error),
+            ));
+          }
+        }
+      };
+    }
+  }
+
+  fn visit_item_mut(&mut self, _: &mut Item) {}
+}
@@ -9,3 +9,20 @@ use proc_macro::TokenStream;
 pub fn evitable(meta: TokenStream, input: TokenStream) -> TokenStream {
   derive_evitable(&parse_macro_input!(meta), &mut parse_macro_input!(input)).into()
 }
+
+/// Wrap the errors returned from a function into an error context, with
+/// the original error as source. The body keeps the declared return type
+/// of the function, so `return Err(..)`, `ensure!` and `fail!` work as
+/// usual, and any error can be returned from the body using `?`. Uses of
+/// `?` inside closures, async blocks and macros in the body are left alone,
+/// and only convert errors through `From` as usual.
+///
+/// The context expression is only evaluated if the body fails, after it
+/// has run. Because of this, it can't refer to parameters that the body
+/// takes ownership of. Prefix the expression with `move` to evaluate it
+/// before the body runs instead, like
+/// `#[with_context(move LoadContext { path: path.clone() })]`.
+#[proc_macro_attribute]
+pub fn with_context(meta: TokenStream, input: TokenStream) -> TokenStream {
+  evitable_derive_core::with_context(&parse_macro_input!(meta), &parse_macro_input!(input)).into()
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+/// Extension trait for `anyhow::Error`, for accessing evitable errors
+/// wrapped by it. Requires the `anyhow` feature.
+///
+/// Evitable errors can be converted into `anyhow::Error` like any other
+/// error, and still be accessed using `downcast_ref`. The methods of
+/// this trait also search the source chain, so they work with errors
+/// wrapped using anyhow's `context` as well.
+///
+/// # Example
+///
+/// ```rust
+/// use evitable::*;
+///
+/// #[evitable]
+/// pub enum Context {
+///   #[evitable(description = "Invalid config")]
+///   InvalidConfig,
+/// }
+///
+/// fn load() -> Result<()> {
+///   fail!(Context::InvalidConfig);
+/// }
+///
+/// # fn main() {
+/// let error = anyhow::Error::from(load().unwrap_err()).context("while starting");
+/// assert_eq!(error.evitable_kind::<ErrorKind>(), Some(ErrorKind::InvalidConfig));
+/// # }
+/// ```
+#[cfg(feature = "anyhow")]
+pub trait EvitableAnyhowExt {
+  /// Get the kind of the first error of the type generated with `K` in
+  /// the source chain, if any.
+  fn evitable_kind<K: EvitableErrorKind + Sized>(&self) -> Option<K>;
+
+  /// Get the first error of type `E` in the source chain, if any.
+  fn evitable_error<E: EvitableError>(&self) -> Option<&E>;
+}
+
+#[cfg(feature = "anyhow")]
+impl EvitableAnyhowExt for ::anyhow::Error {
+  #[inline]
+  fn evitable_kind<K: EvitableErrorKind + Sized>(&self) -> Option<K> {
+    self.chain().find_map(K::from_error)
+  }
+
+  #[inline]
+  fn evitable_error<E: EvitableError>(&self) -> Option<&E> {
+    self.chain().find_map(|e| e.downcast_ref())
+  }
+}
+
+/// Extension trait for `eyre::Report`, for accessing evitable errors
+/// wrapped by it. Requires the `eyre` feature. See
+/// `EvitableAnyhowExt`, which works the same way.
+#[cfg(feature = "eyre")]
+pub trait EvitableEyreExt {
+  /// Get the kind of the first error of the type generated with `K` in
+  /// the source chain, if any.
+  fn evitable_kind<K: EvitableErrorKind + Sized>(&self) -> Option<K>;
+
+  /// Get the first error of type `E` in the source chain, if any.
+  fn evitable_error<E: EvitableError>(&self) -> Option<&E>;
+}
+
+#[cfg(feature = "eyre")]
+impl EvitableEyreExt for ::eyre::Report {
+  #[inline]
+  fn evitable_kind<K: EvitableErrorKind + Sized>(&self) -> Option<K> {
+    self.chain().find_map(K::from_error)
+  }
+
+  #[inline]
+  fn evitable_error<E: EvitableError>(&self) -> Option<&E> {
+    self.chain().find_map(|e| e.downcast_ref())
+  }
+}
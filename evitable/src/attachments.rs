@@ -0,0 +1,148 @@
+use std::any::Any;
+use std::fmt::{self, Debug, Display};
+use std::marker::PhantomData;
+use std::slice;
+
+trait Attachment: Send + Sync {
+  fn as_any(&self) -> &(dyn Any + Send + Sync);
+  fn as_display(&self) -> Option<&dyn Display>;
+}
+
+struct Opaque<T>(T);
+
+impl<T: Send + Sync + 'static> Attachment for Opaque<T> {
+  #[inline]
+  fn as_any(&self) -> &(dyn Any + Send + Sync) {
+    &self.0
+  }
+
+  #[inline]
+  fn as_display(&self) -> Option<&dyn Display> {
+    None
+  }
+}
+
+struct Printable<T>(T);
+
+impl<T: Display + Send + Sync + 'static> Attachment for Printable<T> {
+  #[inline]
+  fn as_any(&self) -> &(dyn Any + Send + Sync) {
+    &self.0
+  }
+
+  #[inline]
+  fn as_display(&self) -> Option<&dyn Display> {
+    Some(&self.0)
+  }
+}
+
+/// Arbitrary typed values attached to an error, like request ids
+/// or retry counts. Every error generated by `#[evitable]` carries
+/// a (typically empty) set of attachments. See
+/// [attach](crate::EvitableError::attach).
+#[derive(Default)]
+pub struct Attachments {
+  // Boxed, as most errors never get any attachments, and errors
+  // should stay small.
+  #[allow(clippy::box_collection)]
+  items: Option<Box<Vec<Box<dyn Attachment>>>>,
+}
+
+impl Attachments {
+  /// Create an empty set of attachments.
+  #[inline]
+  pub fn new() -> Self {
+    Self { items: None }
+  }
+
+  /// Add an attachment. The value is not rendered in reports.
+  #[inline]
+  pub fn push<T: Send + Sync + 'static>(&mut self, value: T) {
+    self
+      .items
+      .get_or_insert_with(Default::default)
+      .push(Box::new(Opaque(value)));
+  }
+
+  /// Add an attachment that is rendered in reports.
+  #[inline]
+  pub fn push_printable<T: Display + Send + Sync + 'static>(&mut self, value: T) {
+    self
+      .items
+      .get_or_insert_with(Default::default)
+      .push(Box::new(Printable(value)));
+  }
+
+  /// Get all attachments of type `T`, in the order they were attached.
+  #[inline]
+  pub fn iter<T: 'static>(&self) -> AttachmentsOf<'_, T> {
+    AttachmentsOf {
+      inner: self.as_slice().iter(),
+      _marker: PhantomData,
+    }
+  }
+
+  /// Get the most recently attached value of type `T`.
+  #[inline]
+  pub fn get<T: 'static>(&self) -> Option<&T> {
+    self.iter().next_back()
+  }
+
+  /// Get all printable attachments, in the order they were attached.
+  pub fn printable(&self) -> impl Iterator<Item = &dyn Display> {
+    self.as_slice().iter().filter_map(|a| a.as_display())
+  }
+
+  /// Get the number of attachments.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.as_slice().len()
+  }
+
+  /// Check if there are no attachments.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.as_slice().is_empty()
+  }
+
+  #[inline]
+  fn as_slice(&self) -> &[Box<dyn Attachment>] {
+    match &self.items {
+      None => &[],
+      Some(items) => items,
+    }
+  }
+}
+
+impl Debug for Attachments {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list()
+      .entries(self.printable().map(|d| d.to_string()))
+      .finish()
+  }
+}
+
+/// Iterator over attachments of a given type. Created by
+/// [Attachments::iter](Attachments::iter).
+pub struct AttachmentsOf<'a, T> {
+  inner: slice::Iter<'a, Box<dyn Attachment>>,
+  _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: 'static> Iterator for AttachmentsOf<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.find_map(|a| a.as_any().downcast_ref())
+  }
+}
+
+impl<'a, T: 'static> DoubleEndedIterator for AttachmentsOf<'a, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self
+      .inner
+      .by_ref()
+      .rev()
+      .find_map(|a| a.as_any().downcast_ref())
+  }
+}
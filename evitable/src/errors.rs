@@ -0,0 +1,200 @@
+use super::*;
+use std::fmt;
+use std::iter::FromIterator;
+use std::slice;
+use std::vec;
+
+/// A collection of errors, used to report several failures at once
+/// (for instance from validation code). See [validate](validate) and
+/// [ResultIteratorExt](ResultIteratorExt).
+///
+/// As there can be several errors, none of them is reported as the
+/// [source](std::error::Error::source). Instead, the `Display` output
+/// renders every error (including its frames and sources), and all errors
+/// are reachable using [iter](Errors::iter).
+pub struct Errors<E: EvitableError> {
+  errors: Vec<E>,
+}
+
+impl<E: EvitableError> Errors<E> {
+  /// Create an empty error collection.
+  #[inline]
+  pub fn new() -> Self {
+    Self { errors: Vec::new() }
+  }
+
+  /// Add an error to the collection.
+  #[inline]
+  pub fn push(&mut self, error: E) {
+    self.errors.push(error);
+  }
+
+  /// Get the number of errors.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.errors.len()
+  }
+
+  /// Check if there are no errors.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.errors.is_empty()
+  }
+
+  /// Get an iterator over the errors, in the order they were added.
+  #[inline]
+  pub fn iter(&self) -> slice::Iter<'_, E> {
+    self.errors.iter()
+  }
+
+  /// Get the errors as a vector.
+  #[inline]
+  pub fn into_vec(self) -> Vec<E> {
+    self.errors
+  }
+
+  /// Convert to a [Result](std::result::Result), which is only an error
+  /// if the collection is not empty.
+  #[inline]
+  pub fn into_result(self) -> Result<(), Self> {
+    if self.is_empty() {
+      Ok(())
+    } else {
+      Err(self)
+    }
+  }
+}
+
+impl<E: EvitableError> Default for Errors<E> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<E: EvitableError> From<E> for Errors<E> {
+  #[inline]
+  fn from(error: E) -> Self {
+    Self {
+      errors: vec![error],
+    }
+  }
+}
+
+impl<E: EvitableError> Extend<E> for Errors<E> {
+  #[inline]
+  fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+    self.errors.extend(iter)
+  }
+}
+
+impl<E: EvitableError> FromIterator<E> for Errors<E> {
+  #[inline]
+  fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+    Self {
+      errors: iter.into_iter().collect(),
+    }
+  }
+}
+
+impl<E: EvitableError> IntoIterator for Errors<E> {
+  type Item = E;
+  type IntoIter = vec::IntoIter<E>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.errors.into_iter()
+  }
+}
+
+impl<'a, E: EvitableError> IntoIterator for &'a Errors<E> {
+  type Item = &'a E;
+  type IntoIter = slice::Iter<'a, E>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.errors.iter()
+  }
+}
+
+impl<E: EvitableError> Display for Errors<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.errors.len() {
+      0 => f.write_str("No errors"),
+      1 => Display::fmt(&self.errors[0], f),
+      n => {
+        write!(f, "{} errors occurred:", n)?;
+        for error in &self.errors {
+          let text = error.to_string();
+          write!(f, "\n  - {}", text.replace('\n', "\n    "))?;
+        }
+
+        Ok(())
+      }
+    }
+  }
+}
+
+impl<E: EvitableError> Debug for Errors<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(&self.errors).finish()
+  }
+}
+
+impl<E: EvitableError> StdError for Errors<E> {}
+
+/// Extension trait for iterators over results, for collecting all
+/// errors instead of stopping at the first one.
+pub trait ResultIteratorExt<T, E: EvitableError>: Iterator<Item = Result<T, E>> + Sized {
+  /// Collect all values, or all errors if there were any.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(description("Invalid value: {}", 0))]
+  ///  pub struct Context(i32);
+  ///
+  ///  fn check(value: i32) -> Result<i32> {
+  ///    ensure!(value >= 0, Context(value));
+  ///    Ok(value)
+  ///  }
+  ///
+  ///# fn main() {
+  ///  let errors = vec![1, -2, 3, -4]
+  ///    .into_iter()
+  ///    .map(check)
+  ///    .collect_errors()
+  ///    .unwrap_err();
+  ///
+  ///  assert_eq!(errors.len(), 2);
+  ///# }
+  /// ```
+  fn collect_errors(self) -> Result<Vec<T>, Errors<E>> {
+    let (values, errors) = self.partition_results();
+    errors.into_result().map(|()| values)
+  }
+
+  /// Split the results into all values and all errors.
+  fn partition_results(self) -> (Vec<T>, Errors<E>) {
+    let mut values = Vec::new();
+    let mut errors = Errors::new();
+    for result in self {
+      match result {
+        Ok(v) => values.push(v),
+        Err(e) => errors.push(e),
+      }
+    }
+
+    (values, errors)
+  }
+}
+
+impl<T, E: EvitableError, I: Iterator<Item = Result<T, E>>> ResultIteratorExt<T, E> for I {}
+
+#[doc(hidden)]
+#[inline]
+#[track_caller]
+pub fn __error_from_context<C: ErrorContext>(context: C) -> C::Error {
+  C::Error::from_context(context)
+}
@@ -0,0 +1,115 @@
+use super::*;
+use std::process::{ExitCode, Termination};
+
+/// Result type for `main` functions, which exits the process with the
+/// [exit code](EvitableError::exit_code) of the error. On failure, the
+/// error (including frames and sources) is printed to stderr, or only its
+/// [user message](ErrorContext::user_message) if selected using
+/// [message](ExitResult::message).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use evitable::*;
+///
+/// #[evitable]
+/// pub enum Context {
+///   #[evitable(description = "Config not found", exit_code = 3)]
+///   ConfigNotFound,
+/// }
+///
+/// fn run() -> Result<()> {
+///   fail!(Context::ConfigNotFound);
+/// }
+///
+/// fn main() -> ExitResult<Error> {
+///   run().into()
+/// }
+/// ```
+#[must_use]
+pub struct ExitResult<E: EvitableError> {
+  result: Result<(), E>,
+  message: Message,
+}
+
+impl<E: EvitableError> ExitResult<E> {
+  /// Create a successful result.
+  #[inline]
+  pub fn ok() -> Self {
+    Ok(()).into()
+  }
+
+  /// Set which message of the error is printed. Defaults to
+  /// [Message::Description](Message::Description), which prints the whole
+  /// error.
+  ///
+  /// # Arguments
+  ///
+  /// * `message` - The message to print
+  #[inline]
+  pub fn message(mut self, message: Message) -> Self {
+    self.message = message;
+    self
+  }
+
+  /// Get the exit code the process will exit with.
+  #[inline]
+  pub fn exit_code(&self) -> u8 {
+    match &self.result {
+      Ok(()) => 0,
+      Err(e) => e.exit_code(),
+    }
+  }
+
+  /// Get the text printed to stderr when exiting, if the result is an
+  /// error. Depends on the selected [message](ExitResult::message).
+  pub fn report_text(&self) -> Option<String> {
+    match &self.result {
+      Ok(()) => None,
+      Err(error) => Some(match self.message {
+        Message::Description => format!("Error: {}", error),
+        Message::User => format!("Error: {}", error.user_message()),
+      }),
+    }
+  }
+
+  /// Get the wrapped result.
+  #[inline]
+  pub fn into_result(self) -> Result<(), E> {
+    self.result
+  }
+}
+
+impl<E: EvitableError> From<Result<(), E>> for ExitResult<E> {
+  #[inline]
+  fn from(result: Result<(), E>) -> Self {
+    ExitResult {
+      result,
+      message: Message::Description,
+    }
+  }
+}
+
+impl<E: EvitableError> From<E> for ExitResult<E> {
+  #[inline]
+  fn from(error: E) -> Self {
+    Err(error).into()
+  }
+}
+
+impl<E: EvitableError> Debug for ExitResult<E> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_tuple("ExitResult").field(&self.result).finish()
+  }
+}
+
+impl<E: EvitableError> Termination for ExitResult<E> {
+  fn report(self) -> ExitCode {
+    let code = self.exit_code();
+    if let Some(text) = self.report_text() {
+      eprintln!("{}", text);
+    }
+
+    ExitCode::from(code)
+  }
+}
@@ -0,0 +1,219 @@
+//! Fault injection for exercising error paths in tests. Requires the
+//! `failpoints` feature; without it, [fail_point](crate::fail_point)
+//! compiles to nothing.
+//!
+//! Fail points are activated either through [enable](enable), or by
+//! setting the `EVITABLE_FAILPOINTS` environment variable to a `;`
+//! separated list of points, using the syntax accepted by
+//! [configure](configure):
+//!
+//! ```text
+//! EVITABLE_FAILPOINTS="read_config;connect=25%;flush=after(3)"
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the environment variable read when the first fail point
+/// is evaluated.
+pub const ENV_VAR: &str = "EVITABLE_FAILPOINTS";
+
+/// When an active fail point triggers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+  /// Fail on every hit.
+  Always,
+
+  /// Fail on a given fraction (between `0.0` and `1.0`) of the hits.
+  Fraction(f64),
+
+  /// Let the first `n` hits pass, and fail on every hit after that.
+  After(u64),
+}
+
+impl fmt::Display for Trigger {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Trigger::Always => f.write_str("always"),
+      Trigger::Fraction(p) => write!(f, "{}%", p * 100.0),
+      Trigger::After(n) => write!(f, "after({})", n),
+    }
+  }
+}
+
+/// Error returned by [configure](configure) for invalid fail point
+/// specifications.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+  spec: String,
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Invalid fail point specification: {:?}", self.spec)
+  }
+}
+
+impl StdError for ParseError {}
+
+struct Point {
+  trigger: Trigger,
+  hits: u64,
+}
+
+struct Registry {
+  points: HashMap<String, Point>,
+  seed: u64,
+}
+
+impl Registry {
+  fn from_env() -> Self {
+    let seed = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|d| d.as_nanos() as u64)
+      .unwrap_or(0);
+
+    let mut registry = Registry {
+      points: HashMap::new(),
+      seed: seed | 1,
+    };
+
+    if let Ok(spec) = std::env::var(ENV_VAR) {
+      for (name, trigger) in parse(&spec).unwrap_or_else(|e| panic!("{}: {}", ENV_VAR, e)) {
+        registry.insert(name, trigger);
+      }
+    }
+
+    registry
+  }
+
+  fn insert(&mut self, name: String, trigger: Trigger) {
+    self.points.insert(name, Point { trigger, hits: 0 });
+  }
+
+  // xorshift64*, good enough to spread failures over hits.
+  fn next_fraction(&mut self) -> f64 {
+    self.seed ^= self.seed >> 12;
+    self.seed ^= self.seed << 25;
+    self.seed ^= self.seed >> 27;
+    let value = self.seed.wrapping_mul(0x2545_f491_4f6c_dd1d);
+    (value >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  fn eval(&mut self, name: &str) -> bool {
+    let trigger = match self.points.get_mut(name) {
+      None => return false,
+      Some(point) => {
+        point.hits += 1;
+        match point.trigger {
+          Trigger::Always => return true,
+          Trigger::After(n) => return point.hits > n,
+          Trigger::Fraction(p) => p,
+        }
+      }
+    };
+
+    self.next_fraction() < trigger
+  }
+}
+
+fn registry() -> MutexGuard<'static, Registry> {
+  static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+  REGISTRY
+    .get_or_init(|| Mutex::new(Registry::from_env()))
+    .lock()
+    .unwrap_or_else(|e| e.into_inner())
+}
+
+fn parse_trigger(spec: &str) -> Option<Trigger> {
+  let spec = spec.trim();
+  if spec.is_empty() || spec == "always" {
+    return Some(Trigger::Always);
+  }
+
+  if let Some(percent) = spec.strip_suffix('%') {
+    let percent: f64 = percent.trim().parse().ok()?;
+    return if (0.0..=100.0).contains(&percent) {
+      Some(Trigger::Fraction(percent / 100.0))
+    } else {
+      None
+    };
+  }
+
+  let hits = spec.strip_prefix("after(")?.strip_suffix(')')?;
+  hits.trim().parse().ok().map(Trigger::After)
+}
+
+fn parse(spec: &str) -> Result<Vec<(String, Trigger)>, ParseError> {
+  let mut points = Vec::new();
+  for item in spec.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+    let (name, trigger) = match item.find('=') {
+      None => (item, ""),
+      Some(idx) => (&item[..idx], &item[idx + 1..]),
+    };
+
+    let name = name.trim();
+    match parse_trigger(trigger) {
+      Some(trigger) if !name.is_empty() => points.push((name.to_owned(), trigger)),
+      _ => {
+        return Err(ParseError {
+          spec: item.to_owned(),
+        })
+      }
+    }
+  }
+
+  Ok(points)
+}
+
+/// Activate the fail point `name`.
+///
+/// # Arguments
+///
+/// * `name` - Name of the fail point
+/// * `trigger` - When the fail point should fail
+pub fn enable(name: impl Into<String>, trigger: Trigger) {
+  registry().insert(name.into(), trigger);
+}
+
+/// Deactivate the fail point `name`.
+pub fn disable(name: &str) {
+  registry().points.remove(name);
+}
+
+/// Deactivate all fail points, including the ones activated
+/// through the environment.
+pub fn reset() {
+  registry().points.clear();
+}
+
+/// Activate fail points from a `;` separated specification, like the
+/// one read from the `EVITABLE_FAILPOINTS` environment variable. Every
+/// item is either just a name (always fail), `name=always`,
+/// `name=N%` (fail on N percent of the hits), or `name=after(N)`
+/// (fail on every hit after the first N).
+///
+/// Nothing is activated if any item is invalid.
+pub fn configure(spec: &str) -> Result<(), ParseError> {
+  let points = parse(spec)?;
+  let mut registry = registry();
+  for (name, trigger) in points {
+    registry.insert(name, trigger);
+  }
+
+  Ok(())
+}
+
+/// Get the number of times the fail point `name` has been hit since
+/// it was activated, or `None` if it isn't active.
+pub fn hits(name: &str) -> Option<u64> {
+  registry().points.get(name).map(|p| p.hits)
+}
+
+#[doc(hidden)]
+pub fn __eval(name: &str) -> bool {
+  registry().eval(name)
+}
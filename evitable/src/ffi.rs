@@ -0,0 +1,204 @@
+//! Passing errors across a C ABI. Requires the `ffi` feature.
+//!
+//! Functions exported to C typically return an `int` status, where `0`
+//! means success. [ffi_guard](ffi_guard) runs a closure returning a
+//! result, and maps the error case to the
+//! [ffi code](crate::EvitableError::ffi_code) of the error, set using
+//! `#[evitable(ffi_code = 2)]`. Errors without a code use
+//! [DEFAULT_CODE](DEFAULT_CODE). The codes [SUCCESS](SUCCESS) (`0`) and
+//! [PANIC_CODE](PANIC_CODE) (`-1`) are reserved, and rejected as
+//! `ffi_code`. The error itself is stored as the "last
+//! error" of the current thread, which C callers retrieve using the
+//! exported functions:
+//!
+//! ```c
+//! int32_t evitable_last_error_code(void);
+//! intptr_t evitable_last_error_message(char *buf, size_t len);
+//! void evitable_clear_error(void);
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use evitable::*;
+//! use evitable::ffi::ffi_guard;
+//!
+//! #[evitable]
+//! pub enum Context {
+//!   #[evitable(description = "Invalid handle", ffi_code = 2)]
+//!   InvalidHandle,
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn my_close(handle: u32) -> i32 {
+//!   ffi_guard(|| -> Result<()> {
+//!     ensure!(handle != 0, Context::InvalidHandle);
+//!     Ok(())
+//!   })
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(my_close(0), 2);
+//! assert_eq!(evitable::ffi::evitable_last_error_code(), 2);
+//! # }
+//! ```
+
+use super::*;
+use std::any::Any;
+use std::cell::RefCell;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// Return code for success.
+pub const SUCCESS: i32 = 0;
+
+/// Return code of [ffi_guard](ffi_guard) when the closure panics.
+pub const PANIC_CODE: i32 = -1;
+
+/// Code of errors without an `ffi_code`.
+pub const DEFAULT_CODE: i32 = 1;
+
+struct LastError {
+  code: i32,
+  message: String,
+}
+
+thread_local! {
+  static LAST_ERROR: RefCell<Option<LastError>> = const { RefCell::new(None) };
+}
+
+/// Store an error as the last error of the current thread, and get its
+/// [ffi code](crate::EvitableError::ffi_code).
+///
+/// # Arguments
+///
+/// * `error` - The error
+pub fn set_last_error<E: EvitableError>(error: &E) -> i32 {
+  let code = error.ffi_code();
+  store(code, error.to_string());
+  code
+}
+
+/// Get the code of the last error of the current thread, or
+/// [SUCCESS](SUCCESS) if there is none.
+#[inline]
+pub fn last_error_code() -> i32 {
+  LAST_ERROR.with(|e| e.borrow().as_ref().map_or(SUCCESS, |e| e.code))
+}
+
+/// Get the message of the last error of the current thread, if any.
+#[inline]
+pub fn last_error_message() -> Option<String> {
+  LAST_ERROR.with(|e| e.borrow().as_ref().map(|e| e.message.clone()))
+}
+
+/// Clear the last error of the current thread.
+#[inline]
+pub fn clear_last_error() {
+  LAST_ERROR.with(|e| *e.borrow_mut() = None);
+}
+
+/// Run a closure, converting its result into a C return code. Returns
+/// [SUCCESS](SUCCESS) if the closure succeeds, and the
+/// [ffi code](crate::EvitableError::ffi_code) of the error otherwise. The
+/// error is stored as the last error of the current thread, and any
+/// previous error is cleared. Values returned by the closure are
+/// dropped; write outputs through pointers instead.
+///
+/// Panics are caught, as unwinding into C is undefined behavior, and
+/// reported as [PANIC_CODE](PANIC_CODE). This includes panics while
+/// formatting the error, and while dropping the returned value or error.
+///
+/// # Arguments
+///
+/// * `f` - The closure
+pub fn ffi_guard<T, E: EvitableError>(f: impl FnOnce() -> Result<T, E>) -> i32 {
+  clear_last_error();
+  let result = panic::catch_unwind(AssertUnwindSafe(|| match f() {
+    Ok(_) => None,
+    Err(error) => Some((error.ffi_code(), error.to_string())),
+  }));
+
+  match result {
+    Ok(None) => SUCCESS,
+    Ok(Some((code, message))) => {
+      store(code, message);
+      code
+    }
+    Err(payload) => {
+      store(PANIC_CODE, format!("panic: {}", panic_message(&*payload)));
+      // The payload itself may panic when dropped.
+      let _ = panic::catch_unwind(AssertUnwindSafe(move || drop(payload)));
+      PANIC_CODE
+    }
+  }
+}
+
+fn store(code: i32, message: String) {
+  LAST_ERROR.with(|e| *e.borrow_mut() = Some(LastError { code, message }));
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+  match payload.downcast_ref::<&'static str>() {
+    Some(s) => s,
+    None => match payload.downcast_ref::<String>() {
+      Some(s) => s,
+      None => "Box<dyn Any>",
+    },
+  }
+}
+
+/// Get the code of the last error of the current thread, or `0` if
+/// there is none.
+#[no_mangle]
+pub extern "C" fn evitable_last_error_code() -> i32 {
+  last_error_code()
+}
+
+/// Copy the message of the last error of the current thread into `buf`,
+/// as a NUL terminated string. Returns the length of the message
+/// (excluding the terminator). If `buf` is null or `len` is too small,
+/// nothing is written, and the required buffer size (including the
+/// terminator) is returned, negated. If there is no error, `0` is
+/// returned, and an empty string is written if `buf` has room for it.
+///
+/// # Safety
+///
+/// `buf` must be null, or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn evitable_last_error_message(buf: *mut c_char, len: usize) -> isize {
+  LAST_ERROR.with(|e| {
+    let last = e.borrow();
+    let message = match last.as_ref() {
+      None => {
+        if !buf.is_null() && len > 0 {
+          // Safety: `buf` is valid for `len > 0` bytes.
+          unsafe { *buf = 0 };
+        }
+
+        return 0;
+      }
+      Some(e) => e.message.as_str(),
+    };
+
+    let required = message.len() + 1;
+    if buf.is_null() || len < required {
+      return -(required as isize);
+    }
+
+    // Safety: `buf` is valid for `len >= required` bytes.
+    unsafe {
+      ptr::copy_nonoverlapping(message.as_ptr() as *const c_char, buf, message.len());
+      *buf.add(message.len()) = 0;
+    }
+
+    message.len() as isize
+  })
+}
+
+/// Clear the last error of the current thread.
+#[no_mangle]
+pub extern "C" fn evitable_clear_error() {
+  clear_last_error()
+}
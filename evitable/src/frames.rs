@@ -0,0 +1,111 @@
+use std::fmt::{self, Debug, Display};
+use std::panic::Location;
+use std::slice;
+
+/// Additional context added to an error after it was created, without
+/// changing its type. See [add_context](crate::EvitableError::add_context).
+pub struct Frame {
+  message: Box<dyn Display + Send + Sync>,
+  location: &'static Location<'static>,
+}
+
+impl Frame {
+  /// Get the message of the frame.
+  #[inline]
+  pub fn message(&self) -> &(dyn Display + Send + Sync) {
+    self.message.as_ref()
+  }
+
+  /// Get the location in the source code where the frame was added.
+  #[inline]
+  pub fn location(&self) -> &'static Location<'static> {
+    self.location
+  }
+}
+
+impl Display for Frame {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    Display::fmt(&self.message, f)
+  }
+}
+
+impl Debug for Frame {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}\n    at {}", self.message, self.location)
+  }
+}
+
+/// Stack of [frames](Frame) added to an error. Every error generated by
+/// `#[evitable]` carries a (typically empty) stack of frames.
+#[derive(Default)]
+pub struct Frames {
+  // Boxed, as most errors never get any frames, and errors should
+  // stay small.
+  #[allow(clippy::box_collection)]
+  frames: Option<Box<Vec<Frame>>>,
+}
+
+impl Frames {
+  /// Create an empty stack of frames.
+  #[inline]
+  pub fn new() -> Self {
+    Self { frames: None }
+  }
+
+  /// Push a new frame onto the stack. The caller location is recorded
+  /// with the frame.
+  #[inline]
+  #[track_caller]
+  pub fn push<D: Display + Send + Sync + 'static>(&mut self, message: D) {
+    self
+      .frames
+      .get_or_insert_with(Default::default)
+      .push(Frame {
+        message: Box::new(message),
+        location: Location::caller(),
+      });
+  }
+
+  /// Get an iterator over the frames, in the order they were added.
+  #[inline]
+  pub fn iter(&self) -> slice::Iter<'_, Frame> {
+    self.as_slice().iter()
+  }
+
+  /// Get the number of frames.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.as_slice().len()
+  }
+
+  /// Check if there are no frames.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.as_slice().is_empty()
+  }
+
+  #[inline]
+  fn as_slice(&self) -> &[Frame] {
+    match &self.frames {
+      None => &[],
+      Some(frames) => frames,
+    }
+  }
+}
+
+impl<'a> IntoIterator for &'a Frames {
+  type Item = &'a Frame;
+  type IntoIter = slice::Iter<'a, Frame>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl Debug for Frames {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
@@ -0,0 +1,268 @@
+//! Extension traits for futures and streams, for easy conversion to
+//! evitable errors in async code. Requires the `futures` feature.
+
+use super::*;
+use futures_core::Stream;
+use std::future::Future;
+use std::panic::Location;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Extension trait for futures resolving to results.
+pub trait FutureExt<T, E, C: ErrorContext>: Future<Output = Result<T, E>> + Sized {
+  /// Convert the error case of the future's output into an evitable error,
+  /// using the given context factory. The factory is only called if the
+  /// future resolves to an error. See [OptionExt::context](crate::OptionExt::context).
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - Error context factory
+  #[track_caller]
+  fn context<F: FnOnce() -> C>(self, f: F) -> ContextFuture<Self, F>;
+
+  /// Convert the error case of the future's output into an evitable error,
+  /// using the given context factory which also accepts a reference to the
+  /// original error. See [ResultExt::context_with](crate::ResultExt::context_with).
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - Error context factory
+  #[track_caller]
+  fn context_with<F: FnOnce(&E) -> C>(self, f: F) -> ContextWithFuture<Self, F>;
+}
+
+impl<T, E, C, Fut> FutureExt<T, E, C> for Fut
+where
+  E: StdError + Send + Sync + 'static,
+  C: ErrorContext,
+  Fut: Future<Output = Result<T, E>>,
+{
+  #[inline]
+  #[track_caller]
+  fn context<F: FnOnce() -> C>(self, f: F) -> ContextFuture<Self, F> {
+    ContextFuture {
+      future: self,
+      f: Some(f),
+      location: Location::caller(),
+    }
+  }
+
+  #[inline]
+  #[track_caller]
+  fn context_with<F: FnOnce(&E) -> C>(self, f: F) -> ContextWithFuture<Self, F> {
+    ContextWithFuture {
+      future: self,
+      f: Some(f),
+      location: Location::caller(),
+    }
+  }
+}
+
+/// Future for [FutureExt::context](FutureExt::context).
+#[must_use = "futures do nothing unless polled"]
+pub struct ContextFuture<Fut, F> {
+  future: Fut,
+  f: Option<F>,
+  location: &'static Location<'static>,
+}
+
+impl<T, E, C, Fut, F> Future for ContextFuture<Fut, F>
+where
+  E: StdError + Send + Sync + 'static,
+  C: ErrorContext,
+  Fut: Future<Output = Result<T, E>>,
+  F: FnOnce() -> C,
+{
+  type Output = Result<T, C::Error>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    // Safety: `future` is structurally pinned, `f` is never pinned.
+    let this = unsafe { self.get_unchecked_mut() };
+    let future = unsafe { Pin::new_unchecked(&mut this.future) };
+    match future.poll(cx) {
+      Poll::Pending => Poll::Pending,
+      Poll::Ready(Ok(v)) => Poll::Ready(Ok(v)),
+      Poll::Ready(Err(e)) => {
+        let f = this
+          .f
+          .take()
+          .expect("ContextFuture polled after completion");
+        Poll::Ready(Err(C::Error::from_error_context_at(f(), e, this.location)))
+      }
+    }
+  }
+}
+
+/// Future for [FutureExt::context_with](FutureExt::context_with).
+#[must_use = "futures do nothing unless polled"]
+pub struct ContextWithFuture<Fut, F> {
+  future: Fut,
+  f: Option<F>,
+  location: &'static Location<'static>,
+}
+
+impl<T, E, C, Fut, F> Future for ContextWithFuture<Fut, F>
+where
+  E: StdError + Send + Sync + 'static,
+  C: ErrorContext,
+  Fut: Future<Output = Result<T, E>>,
+  F: FnOnce(&E) -> C,
+{
+  type Output = Result<T, C::Error>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    // Safety: `future` is structurally pinned, `f` is never pinned.
+    let this = unsafe { self.get_unchecked_mut() };
+    let future = unsafe { Pin::new_unchecked(&mut this.future) };
+    match future.poll(cx) {
+      Poll::Pending => Poll::Pending,
+      Poll::Ready(Ok(v)) => Poll::Ready(Ok(v)),
+      Poll::Ready(Err(e)) => {
+        let f = this
+          .f
+          .take()
+          .expect("ContextWithFuture polled after completion");
+        let context = f(&e);
+        Poll::Ready(Err(C::Error::from_error_context_at(
+          context,
+          e,
+          this.location,
+        )))
+      }
+    }
+  }
+}
+
+/// Extension trait for streams of results.
+pub trait TryStreamExt<T, E, C: ErrorContext>: Stream<Item = Result<T, E>> + Sized {
+  /// Convert every error yielded by the stream into an evitable error,
+  /// using the given context factory. The factory is only called for
+  /// errors.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - Error context factory
+  #[track_caller]
+  fn context<F: FnMut() -> C>(self, f: F) -> ContextStream<Self, F>;
+
+  /// Convert every error yielded by the stream into an evitable error,
+  /// using the given context factory which also accepts a reference to
+  /// the original error.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - Error context factory
+  #[track_caller]
+  fn context_with<F: FnMut(&E) -> C>(self, f: F) -> ContextWithStream<Self, F>;
+}
+
+impl<T, E, C, S> TryStreamExt<T, E, C> for S
+where
+  E: StdError + Send + Sync + 'static,
+  C: ErrorContext,
+  S: Stream<Item = Result<T, E>>,
+{
+  #[inline]
+  #[track_caller]
+  fn context<F: FnMut() -> C>(self, f: F) -> ContextStream<Self, F> {
+    ContextStream {
+      stream: self,
+      f,
+      location: Location::caller(),
+    }
+  }
+
+  #[inline]
+  #[track_caller]
+  fn context_with<F: FnMut(&E) -> C>(self, f: F) -> ContextWithStream<Self, F> {
+    ContextWithStream {
+      stream: self,
+      f,
+      location: Location::caller(),
+    }
+  }
+}
+
+/// Stream for [TryStreamExt::context](TryStreamExt::context).
+#[must_use = "streams do nothing unless polled"]
+pub struct ContextStream<S, F> {
+  stream: S,
+  f: F,
+  location: &'static Location<'static>,
+}
+
+impl<T, E, C, S, F> Stream for ContextStream<S, F>
+where
+  E: StdError + Send + Sync + 'static,
+  C: ErrorContext,
+  S: Stream<Item = Result<T, E>>,
+  F: FnMut() -> C,
+{
+  type Item = Result<T, C::Error>;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    // Safety: `stream` is structurally pinned, `f` is never pinned.
+    let this = unsafe { self.get_unchecked_mut() };
+    let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+    match stream.poll_next(cx) {
+      Poll::Pending => Poll::Pending,
+      Poll::Ready(None) => Poll::Ready(None),
+      Poll::Ready(Some(Ok(v))) => Poll::Ready(Some(Ok(v))),
+      Poll::Ready(Some(Err(e))) => {
+        let context = (this.f)();
+        Poll::Ready(Some(Err(C::Error::from_error_context_at(
+          context,
+          e,
+          this.location,
+        ))))
+      }
+    }
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.stream.size_hint()
+  }
+}
+
+/// Stream for [TryStreamExt::context_with](TryStreamExt::context_with).
+#[must_use = "streams do nothing unless polled"]
+pub struct ContextWithStream<S, F> {
+  stream: S,
+  f: F,
+  location: &'static Location<'static>,
+}
+
+impl<T, E, C, S, F> Stream for ContextWithStream<S, F>
+where
+  E: StdError + Send + Sync + 'static,
+  C: ErrorContext,
+  S: Stream<Item = Result<T, E>>,
+  F: FnMut(&E) -> C,
+{
+  type Item = Result<T, C::Error>;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    // Safety: `stream` is structurally pinned, `f` is never pinned.
+    let this = unsafe { self.get_unchecked_mut() };
+    let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+    match stream.poll_next(cx) {
+      Poll::Pending => Poll::Pending,
+      Poll::Ready(None) => Poll::Ready(None),
+      Poll::Ready(Some(Ok(v))) => Poll::Ready(Some(Ok(v))),
+      Poll::Ready(Some(Err(e))) => {
+        let context = (this.f)(&e);
+        Poll::Ready(Some(Err(C::Error::from_error_context_at(
+          context,
+          e,
+          this.location,
+        ))))
+      }
+    }
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.stream.size_hint()
+  }
+}
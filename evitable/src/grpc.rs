@@ -0,0 +1,179 @@
+//! gRPC status codes for errors. The code of an error is set using
+//! `#[evitable(grpc_code = "NotFound")]` (on the context type, or on
+//! individual variants), and defaults to [Unknown](Code::Unknown). [Ok](Code::Ok)
+//! is reserved for success, and rejected at compile time.
+//!
+//! With the `tonic` feature enabled, all errors generated by `#[evitable]`
+//! implement `From<Error> for tonic::Status`. The status carries the code
+//! and the error message, as well as the kind name and the
+//! [error code](crate::EvitableError::code) as metadata. Use
+//! [kind_from_status](kind_from_status) to get the kind back on the
+//! receiving side.
+//!
+//! # Example
+//!
+//! ```rust
+//! use evitable::*;
+//! use evitable::grpc::Code;
+//!
+//! #[evitable(grpc_code = "Internal")]
+//! pub enum Context {
+//!   #[evitable(description("User {} not found", 0), grpc_code = "NotFound")]
+//!   UserNotFound(u64),
+//!
+//!   #[evitable(description = "Database is down")]
+//!   Database,
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(Context::UserNotFound(42).grpc_code(), Code::NotFound);
+//! assert_eq!(ErrorKind::Database.grpc_code(), Code::Internal);
+//! # }
+//! ```
+
+use super::*;
+
+/// Name of the status metadata entry holding the [kind name](crate::EvitableErrorKind::name).
+pub const KIND_METADATA_KEY: &str = "evitable-kind";
+
+/// Name of the status metadata entry holding the [error code](crate::EvitableError::code).
+pub const CODE_METADATA_KEY: &str = "evitable-code";
+
+/// gRPC status codes, as defined by the
+/// [gRPC specification](https://github.com/grpc/grpc/blob/master/doc/statuscodes.md).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Code {
+  /// The operation completed successfully.
+  Ok = 0,
+  /// The operation was cancelled.
+  Cancelled = 1,
+  /// Unknown error.
+  Unknown = 2,
+  /// Client specified an invalid argument.
+  InvalidArgument = 3,
+  /// Deadline expired before operation could complete.
+  DeadlineExceeded = 4,
+  /// Some requested entity was not found.
+  NotFound = 5,
+  /// Some entity that we attempted to create already exists.
+  AlreadyExists = 6,
+  /// The caller does not have permission to execute the specified operation.
+  PermissionDenied = 7,
+  /// Some resource has been exhausted.
+  ResourceExhausted = 8,
+  /// The system is not in a state required for the operation's execution.
+  FailedPrecondition = 9,
+  /// The operation was aborted.
+  Aborted = 10,
+  /// Operation was attempted past the valid range.
+  OutOfRange = 11,
+  /// Operation is not implemented or not supported.
+  Unimplemented = 12,
+  /// Internal error.
+  Internal = 13,
+  /// The service is currently unavailable.
+  Unavailable = 14,
+  /// Unrecoverable data loss or corruption.
+  DataLoss = 15,
+  /// The request does not have valid authentication credentials.
+  Unauthenticated = 16,
+}
+
+impl Code {
+  /// Get the numeric value of the code.
+  #[inline]
+  pub fn as_i32(self) -> i32 {
+    self as i32
+  }
+}
+
+impl Display for Code {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    Debug::fmt(self, f)
+  }
+}
+
+#[cfg(feature = "tonic")]
+impl From<Code> for tonic::Code {
+  #[inline]
+  fn from(code: Code) -> Self {
+    tonic::Code::from_i32(code.as_i32())
+  }
+}
+
+/// Convert an error to a `tonic::Status`. This is what the generated
+/// `From<Error> for tonic::Status` implementations use.
+///
+/// # Arguments
+///
+/// * `error` - The error
+#[cfg(feature = "tonic")]
+pub fn to_status<E: EvitableError>(error: &E) -> tonic::Status {
+  use std::convert::TryFrom;
+  use tonic::metadata::MetadataValue;
+
+  let mut status = tonic::Status::new(error.grpc_code().into(), error.to_string());
+  let metadata = status.metadata_mut();
+  if let Ok(kind) = MetadataValue::try_from(error.kind().name()) {
+    metadata.insert(KIND_METADATA_KEY, kind);
+  }
+
+  if let Some(Ok(code)) = error.code().map(MetadataValue::try_from) {
+    metadata.insert(CODE_METADATA_KEY, code);
+  }
+
+  status
+}
+
+/// Get the error kind from a `tonic::Status` created from an error of
+/// kind `K`. Only kinds without fields can be recreated; for other kinds
+/// (and statuses not created by evitable) this returns `None`.
+///
+/// # Arguments
+///
+/// * `status` - The received status
+///
+/// # Example
+///
+/// ```rust
+/// use evitable::*;
+/// use evitable::grpc::kind_from_status;
+///
+/// #[evitable(grpc_code = "NotFound")]
+/// pub enum Context {
+///   #[evitable(description = "User not found")]
+///   UserNotFound,
+/// }
+///
+/// # fn main() {
+/// let status = tonic::Status::from(Error::from_context(Context::UserNotFound));
+/// assert_eq!(status.code(), tonic::Code::NotFound);
+/// assert_eq!(kind_from_status::<ErrorKind>(&status), Some(ErrorKind::UserNotFound));
+/// # }
+/// ```
+#[cfg(feature = "tonic")]
+pub fn kind_from_status<K: EvitableErrorKind + Sized>(status: &tonic::Status) -> Option<K> {
+  let name = status.metadata().get(KIND_METADATA_KEY)?.to_str().ok()?;
+  K::from_name(name)
+}
+
+#[cfg(feature = "tonic")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_tonic {
+  ($error:ident) => {
+    impl ::std::convert::From<$error> for $crate::__private::tonic::Status {
+      #[inline]
+      fn from(error: $error) -> Self {
+        $crate::grpc::to_status(&error)
+      }
+    }
+  };
+}
+
+#[cfg(not(feature = "tonic"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_tonic {
+  ($error:ident) => {};
+}
@@ -0,0 +1,201 @@
+use super::*;
+use std::any::type_name;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+type Hook = Arc<dyn Fn(&dyn ErrorInfo) + Send + Sync + 'static>;
+
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+/// Whether a global hook is installed, so creating errors doesn't have to
+/// take the lock when there is none.
+static HOOK_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+  static LOCAL_HOOK: RefCell<Option<Hook>> = const { RefCell::new(None) };
+  static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Information about a newly created error, passed to the hook set
+/// using [set_hook](set_hook).
+pub trait ErrorInfo {
+  /// Get the name of the error kind, like `"NotFound"`.
+  fn kind_name(&self) -> &'static str;
+
+  /// Get the code of the error, set using `#[evitable(code = "...")]`.
+  fn code(&self) -> Option<&'static str>;
+
+  /// Get the severity of the error.
+  fn severity(&self) -> Severity;
+
+  /// Get the name of the error type.
+  fn type_name(&self) -> &'static str;
+
+  /// Get the location in the source code where the error was created.
+  fn location(&self) -> &'static Location<'static>;
+
+  /// Get the error context.
+  fn context(&self) -> &dyn Display;
+
+  /// Get the error itself.
+  fn error(&self) -> &(dyn StdError + 'static);
+}
+
+struct Info<'a, E>(&'a E);
+
+impl<'a, E: EvitableError> ErrorInfo for Info<'a, E> {
+  #[inline]
+  fn kind_name(&self) -> &'static str {
+    self.0.kind().name()
+  }
+
+  #[inline]
+  fn code(&self) -> Option<&'static str> {
+    self.0.code()
+  }
+
+  #[inline]
+  fn severity(&self) -> Severity {
+    self.0.severity()
+  }
+
+  #[inline]
+  fn type_name(&self) -> &'static str {
+    type_name::<E>()
+  }
+
+  #[inline]
+  fn location(&self) -> &'static Location<'static> {
+    self.0.location()
+  }
+
+  #[inline]
+  fn context(&self) -> &dyn Display {
+    self.0.context()
+  }
+
+  #[inline]
+  fn error(&self) -> &(dyn StdError + 'static) {
+    self.0
+  }
+}
+
+/// Register a hook called every time an error generated by `#[evitable]`
+/// is created, for instance to count errors by kind. Replaces any
+/// previously registered hook. Errors created from within the hook do
+/// not trigger it again.
+///
+/// # Arguments
+///
+/// * `hook` - The hook
+///
+/// # Example
+///
+/// ```rust
+///# use evitable::*;
+///# use std::sync::atomic::{AtomicUsize, Ordering};
+///  #[evitable(description = "Error")]
+///  pub struct Context;
+///
+///  static COUNT: AtomicUsize = AtomicUsize::new(0);
+///
+///# fn main() {
+///  set_hook(|info: &dyn ErrorInfo| {
+///    if info.kind_name() == "Context" {
+///      COUNT.fetch_add(1, Ordering::Relaxed);
+///    }
+///  });
+///
+///  let _ = Error::from_context(Context);
+///  assert_eq!(COUNT.load(Ordering::Relaxed), 1);
+///# }
+/// ```
+pub fn set_hook<F: Fn(&dyn ErrorInfo) + Send + Sync + 'static>(hook: F) {
+  let mut guard = HOOK.write().unwrap_or_else(|e| e.into_inner());
+  *guard = Some(Arc::new(hook));
+  HOOK_INSTALLED.store(true, Ordering::Release);
+}
+
+/// Unregister the hook registered using [set_hook](set_hook).
+pub fn clear_hook() {
+  let mut guard = HOOK.write().unwrap_or_else(|e| e.into_inner());
+  *guard = None;
+  HOOK_INSTALLED.store(false, Ordering::Release);
+}
+
+/// Run `f` with a hook that only applies to errors created on the current
+/// thread, instead of the one registered using [set_hook](set_hook). This
+/// is mostly useful in tests, which run in parallel.
+///
+/// # Arguments
+///
+/// * `hook` - The hook
+/// * `f` - Function to run with the hook
+///
+/// # Example
+///
+/// ```rust
+///# use evitable::*;
+///# use std::sync::atomic::{AtomicUsize, Ordering};
+///# use std::sync::Arc;
+///  #[evitable(description = "Error")]
+///  pub struct Context;
+///
+///# fn main() {
+///  let count = Arc::new(AtomicUsize::new(0));
+///  let hook_count = count.clone();
+///  let hook = move |_: &dyn ErrorInfo| {
+///    hook_count.fetch_add(1, Ordering::Relaxed);
+///  };
+///
+///  with_hook(hook, || Error::from_context(Context));
+///  assert_eq!(count.load(Ordering::Relaxed), 1);
+///# }
+/// ```
+pub fn with_hook<F, R>(hook: F, f: impl FnOnce() -> R) -> R
+where
+  F: Fn(&dyn ErrorInfo) + Send + Sync + 'static,
+{
+  struct Restore(Option<Hook>);
+
+  impl Drop for Restore {
+    fn drop(&mut self) {
+      let previous = self.0.take();
+      LOCAL_HOOK.with(|h| *h.borrow_mut() = previous);
+    }
+  }
+
+  let previous = LOCAL_HOOK.with(|h| h.borrow_mut().replace(Arc::new(hook)));
+  let _restore = Restore(previous);
+  f()
+}
+
+#[doc(hidden)]
+pub fn __run_hook<E: EvitableError>(error: &E) {
+  if IN_HOOK.with(Cell::get) {
+    return;
+  }
+
+  let hook = match LOCAL_HOOK.with(|h| h.borrow().clone()) {
+    Some(hook) => Some(hook),
+    None if HOOK_INSTALLED.load(Ordering::Acquire) => {
+      HOOK.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+    None => None,
+  };
+
+  if let Some(hook) = hook {
+    struct Reset;
+
+    impl Drop for Reset {
+      fn drop(&mut self) {
+        IN_HOOK.with(|h| h.set(false));
+      }
+    }
+
+    IN_HOOK.with(|h| h.set(true));
+    let _reset = Reset;
+    hook(&Info(error));
+  }
+}
@@ -0,0 +1,282 @@
+//! HTTP integration. Contexts and variants are mapped to HTTP status codes
+//! using `#[evitable(http_status = 404)]` (errors are `500` by default),
+//! and rendered as a JSON [ErrorBody](ErrorBody).
+//!
+//! With the `axum` feature, every error generated by `#[evitable]`
+//! implements `axum::response::IntoResponse`, and with the `actix-web`
+//! feature it implements `actix_web::ResponseError`. Both respond with the
+//! status of the error, and its [ErrorBody](ErrorBody) as JSON. To respond
+//! with the [user message](crate::ErrorContext::user_message) instead of
+//! the description, add `#[evitable(http_message = "user")]` to the
+//! context.
+//!
+//! # Example
+//!
+//! ```rust
+//! use evitable::*;
+//! use evitable::http::ErrorBody;
+//!
+//! #[evitable]
+//! pub enum Context {
+//!   #[evitable(description("User {} not found", 0), http_status = 404, code = "USER_NOT_FOUND")]
+//!   UserNotFound(u64),
+//!
+//!   #[evitable(description("Database at {} is down", 0))]
+//!   Database(&'static str),
+//! }
+//!
+//! # fn main() {
+//! let error = Error::from_context(Context::UserNotFound(42));
+//! assert_eq!(error.http_status(), 404);
+//! assert_eq!(ErrorBody::new(&error).message(), "User 42 not found");
+//!
+//! let error = Error::from_context(Context::Database("10.0.0.1"));
+//! assert_eq!(error.http_status(), 500);
+//! assert_eq!(ErrorBody::new(&error).message(), "Internal server error");
+//! # }
+//! ```
+//!
+//! Using [ErrorBody::new_with](ErrorBody::new_with), bodies can show the
+//! [user message](crate::ErrorContext::user_message) of errors instead of
+//! their description. User messages are meant to be shown to end users, so
+//! they are included regardless of the status.
+
+use super::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static EXPOSE_DETAILS: AtomicBool = AtomicBool::new(false);
+
+/// Message used instead of the error context for internal errors.
+pub const INTERNAL_ERROR_MESSAGE: &str = "Internal server error";
+
+/// Set whether the messages of internal errors (with a `5xx` status) are
+/// included in [ErrorBody](ErrorBody)s. They are hidden by default, as they
+/// can leak implementation details. Typically only enabled in development.
+///
+/// # Arguments
+///
+/// * `expose` - Whether to include the messages of internal errors
+pub fn set_expose_details(expose: bool) {
+  EXPOSE_DETAILS.store(expose, Ordering::Relaxed);
+}
+
+/// Check whether the messages of internal errors are included in
+/// [ErrorBody](ErrorBody)s. See [set_expose_details](set_expose_details).
+pub fn expose_details() -> bool {
+  EXPOSE_DETAILS.load(Ordering::Relaxed)
+}
+
+/// Check if an HTTP status denotes an internal error.
+#[inline]
+pub fn is_internal(status: u16) -> bool {
+  status >= 500
+}
+
+/// Body of HTTP error responses, containing the kind, code and message
+/// of an error. Serialized as a JSON object with the fields `kind`,
+/// `code` (omitted if the error has no code) and `message`, if the `serde`
+/// feature is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorBody {
+  status: u16,
+  kind: &'static str,
+  code: Option<&'static str>,
+  message: String,
+}
+
+impl ErrorBody {
+  /// Create the body for an error. The message of internal errors is
+  /// replaced with a generic one, unless [set_expose_details](set_expose_details)
+  /// is enabled.
+  ///
+  /// # Arguments
+  ///
+  /// * `error` - The error
+  #[inline]
+  pub fn new<E: EvitableError>(error: &E) -> Self {
+    Self::new_with(error, Message::Description)
+  }
+
+  /// Create the body for an error, with the given message. Descriptions
+  /// of internal errors are replaced with a generic message, unless
+  /// [set_expose_details](set_expose_details) is enabled. User messages
+  /// are included regardless of the status.
+  ///
+  /// # Arguments
+  ///
+  /// * `error` - The error
+  /// * `message` - The message to include
+  pub fn new_with<E: EvitableError>(error: &E, message: Message) -> Self {
+    match message {
+      Message::User => Self::with_user_message(error),
+      Message::Description if is_internal(error.http_status()) && !expose_details() => {
+        Self::with_message(error, INTERNAL_ERROR_MESSAGE.to_owned())
+      }
+      Message::Description => Self::with_details(error),
+    }
+  }
+
+  /// Create the body for an error, including the message of the error
+  /// context regardless of the status.
+  ///
+  /// # Arguments
+  ///
+  /// * `error` - The error
+  pub fn with_details<E: EvitableError>(error: &E) -> Self {
+    Self::with_message(error, error.context().to_string())
+  }
+
+  /// Create the body for an error, using its
+  /// [user message](crate::ErrorContext::user_message) regardless of the
+  /// status.
+  ///
+  /// # Arguments
+  ///
+  /// * `error` - The error
+  pub fn with_user_message<E: EvitableError>(error: &E) -> Self {
+    Self::with_message(error, error.user_message().to_string())
+  }
+
+  fn with_message<E: EvitableError>(error: &E, message: String) -> Self {
+    Self {
+      status: error.http_status(),
+      kind: error.kind().name(),
+      code: error.code(),
+      message,
+    }
+  }
+
+  /// Get the HTTP status of the error.
+  #[inline]
+  pub fn status(&self) -> u16 {
+    self.status
+  }
+
+  /// Get the name of the error kind.
+  #[inline]
+  pub fn kind(&self) -> &'static str {
+    self.kind
+  }
+
+  /// Get the code of the error.
+  #[inline]
+  pub fn code(&self) -> Option<&'static str> {
+    self.code
+  }
+
+  /// Get the message of the error.
+  #[inline]
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Serialize the body as JSON. Requires the `serde` feature.
+  #[cfg(feature = "serde")]
+  pub fn to_json(&self) -> Vec<u8> {
+    serde_json::to_vec(self).expect("ErrorBody is always serializable")
+  }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorBody {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeStruct;
+
+    let len = if self.code.is_some() { 3 } else { 2 };
+    let mut s = serializer.serialize_struct("ErrorBody", len)?;
+    s.serialize_field("kind", self.kind)?;
+    match self.code {
+      Some(code) => s.serialize_field("code", code)?,
+      None => s.skip_field("code")?,
+    }
+    s.serialize_field("message", &self.message)?;
+    s.end()
+  }
+}
+
+#[cfg(feature = "axum")]
+#[doc(hidden)]
+pub fn __axum_response<E: EvitableError>(error: &E, message: Message) -> axum::response::Response {
+  use axum::http::{header, HeaderValue, StatusCode};
+  use axum::response::IntoResponse;
+
+  let body = ErrorBody::new_with(error, message);
+  let status = StatusCode::from_u16(body.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+  let content_type = HeaderValue::from_static("application/json");
+  (
+    status,
+    [(header::CONTENT_TYPE, content_type)],
+    body.to_json(),
+  )
+    .into_response()
+}
+
+#[cfg(feature = "actix-web")]
+#[doc(hidden)]
+pub fn __actix_status<E: EvitableError>(error: &E) -> actix_web::http::StatusCode {
+  actix_web::http::StatusCode::from_u16(error.http_status())
+    .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[cfg(feature = "actix-web")]
+#[doc(hidden)]
+pub fn __actix_response<E: EvitableError>(error: &E, message: Message) -> actix_web::HttpResponse {
+  actix_web::HttpResponse::build(__actix_status(error))
+    .content_type("application/json")
+    .body(ErrorBody::new_with(error, message).to_json())
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_web {
+  ($error:ty, $message:expr) => {
+    $crate::__impl_axum!($error, $message);
+    $crate::__impl_actix_web!($error, $message);
+  };
+}
+
+#[cfg(feature = "axum")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_axum {
+  ($error:ty, $message:expr) => {
+    #[automatically_derived]
+    impl $crate::__private::axum::response::IntoResponse for $error {
+      fn into_response(self) -> $crate::__private::axum::response::Response {
+        $crate::http::__axum_response(&self, $message)
+      }
+    }
+  };
+}
+
+#[cfg(not(feature = "axum"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_axum {
+  ($error:ty, $message:expr) => {};
+}
+
+#[cfg(feature = "actix-web")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_actix_web {
+  ($error:ty, $message:expr) => {
+    #[automatically_derived]
+    impl $crate::__private::actix_web::ResponseError for $error {
+      fn status_code(&self) -> $crate::__private::actix_web::http::StatusCode {
+        $crate::http::__actix_status(self)
+      }
+
+      fn error_response(&self) -> $crate::__private::actix_web::HttpResponse {
+        $crate::http::__actix_response(self, $message)
+      }
+    }
+  };
+}
+
+#[cfg(not(feature = "actix-web"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_actix_web {
+  ($error:ty, $message:expr) => {};
+}
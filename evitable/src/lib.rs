@@ -70,19 +70,186 @@ extern crate backtrace;
 extern crate evitable_derive;
 
 use std::fmt::{Debug, Display};
+use std::io;
+use std::panic::Location;
+use std::time::Duration;
+
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+mod anyhow_ext;
+mod attachments;
+mod errors;
+mod exit;
+#[cfg(feature = "failpoints")]
+pub mod failpoints;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod frames;
+#[cfg(feature = "futures")]
+pub mod futures;
+pub mod grpc;
+mod hook;
+pub mod http;
+mod message;
+#[cfg(feature = "serde")]
+pub mod problem;
+mod redact;
+pub mod retry;
+mod severity;
+mod span_trace;
+pub mod testing;
 
 #[cfg(feature = "derive")]
-pub use evitable_derive::evitable;
+pub use evitable_derive::{evitable, with_context};
+
+#[cfg(feature = "anyhow")]
+pub use anyhow_ext::EvitableAnyhowExt;
+#[cfg(feature = "eyre")]
+pub use anyhow_ext::EvitableEyreExt;
+pub use attachments::{Attachments, AttachmentsOf};
+#[doc(hidden)]
+pub use errors::__error_from_context;
+pub use errors::{Errors, ResultIteratorExt};
+pub use exit::ExitResult;
+pub use frames::{Frame, Frames};
+#[doc(hidden)]
+pub use hook::__run_hook;
+pub use hook::{clear_hook, set_hook, with_hook, ErrorInfo};
+pub use message::{Message, UserMessage, DEFAULT_USER_MESSAGE};
+#[doc(hidden)]
+pub use redact::Redacted;
+pub use redact::{Unredacted, UnredactedError};
+pub use severity::Severity;
+pub use span_trace::SpanTrace;
 
 pub use backtrace::Backtrace;
 #[doc(hidden)]
 pub use std::error::Error as StdError;
 
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_kind_fields {
+  ($($tt:tt)*) => {};
+}
+
+#[doc(hidden)]
+pub mod __private {
+  #[cfg(feature = "actix-web")]
+  pub use actix_web;
+  #[cfg(feature = "axum")]
+  pub use axum;
+  #[cfg(feature = "tonic")]
+  pub use tonic;
+}
+
 /// Trait for "error kinds". An `ErrorKind` enum is generated for
 /// every `#[evitable]` type which typically just contains
 /// variants for each error variant (or just a single variant in
 /// case of error structs).
-pub trait EvitableErrorKind: PartialEq + Display {}
+pub trait EvitableErrorKind: PartialEq + Display {
+  /// Get the name of the kind, like `"NotFound"`.
+  fn name(&self) -> &'static str;
+
+  /// Get the severity of errors of this kind. See [Severity](Severity).
+  #[inline]
+  fn severity(&self) -> Severity {
+    Severity::Error
+  }
+
+  /// Get the code of errors of this kind, set using
+  /// `#[evitable(code = "...")]`.
+  #[inline]
+  fn code(&self) -> Option<&'static str> {
+    None
+  }
+
+  /// Check if errors of this kind are marked with
+  /// `#[evitable(retryable)]`.
+  #[inline]
+  fn is_retryable(&self) -> bool {
+    false
+  }
+
+  /// Get the HTTP status of errors of this kind, set using
+  /// `#[evitable(http_status = 404)]`. Defaults to `500`.
+  #[inline]
+  fn http_status(&self) -> u16 {
+    500
+  }
+
+  /// Get the gRPC status code of errors of this kind, set using
+  /// `#[evitable(grpc_code = "NotFound")]`. Defaults to
+  /// [Unknown](grpc::Code::Unknown).
+  #[inline]
+  fn grpc_code(&self) -> grpc::Code {
+    grpc::Code::Unknown
+  }
+
+  /// Get the process exit code of errors of this kind, set using
+  /// `#[evitable(exit_code = 3)]`. Defaults to `1`. Never `0`, which is
+  /// reserved for success.
+  #[inline]
+  fn exit_code(&self) -> u8 {
+    1
+  }
+
+  /// Get the [io::ErrorKind](std::io::ErrorKind) of errors of this kind,
+  /// set using `#[evitable(io_kind = NotFound)]`. Defaults to
+  /// [Other](std::io::ErrorKind::Other).
+  #[inline]
+  fn io_kind(&self) -> io::ErrorKind {
+    io::ErrorKind::Other
+  }
+
+  /// Get the C ABI error code of errors of this kind, set using
+  /// `#[evitable(ffi_code = 2)]`. Defaults to `1`. Never `0` or `-1`,
+  /// which are reserved for success and panics.
+  #[inline]
+  fn ffi_code(&self) -> i32 {
+    1
+  }
+
+  /// Get the kind with the given [name](EvitableErrorKind::name). Only
+  /// kinds without fields can be created this way.
+  #[inline]
+  fn from_name(_name: &str) -> Option<Self>
+  where
+    Self: Sized,
+  {
+    None
+  }
+
+  /// Get the kind of an error, if it is an error of the type generated
+  /// together with this kind. Useful when working with boxed errors (or
+  /// error reporting types like `anyhow::Error`).
+  ///
+  /// # Arguments
+  ///
+  /// * `error` - The error
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable]
+  ///  pub enum Context {
+  ///    #[evitable(description = "Not found")]
+  ///    NotFound,
+  ///  }
+  ///
+  ///# fn main() {
+  ///  let boxed: Box<dyn StdError> = Box::new(Error::from_context(Context::NotFound));
+  ///  assert_eq!(ErrorKind::from_error(boxed.as_ref()), Some(ErrorKind::NotFound));
+  ///# }
+  /// ```
+  #[inline]
+  fn from_error(_error: &(dyn StdError + 'static)) -> Option<Self>
+  where
+    Self: Sized,
+  {
+    None
+  }
+}
 
 /// Trait implemented for all error types generated by `#[evitable]`.
 /// Allows for creating new errors from the [ErrorContext](ErrorContext), and
@@ -95,7 +262,8 @@ pub trait EvitableError: StdError + Sized + Send + Sync + 'static {
   type Context: ErrorContext<Error = Self, Kind = Self::Kind>;
 
   /// Create a new error instance, based on an error context and an optional source error.
-  /// Instead of using this directly, see [from_error_context](EvitableError::from_error_context) when
+  /// The caller location is recorded, and can later be retrieved using
+  /// [location](EvitableError::location). Instead of using this directly, see [from_error_context](EvitableError::from_error_context) when
   /// wanting to create error instances with source errors, and [from_context](EvitableError::from_context)
   /// when not. Derived implementations of this trait also implements `From<ErrorContext>`, so using
   /// [from](std::convert::From::from) or [into](std::convert::Into::into) is also an option.
@@ -117,8 +285,30 @@ pub trait EvitableError: StdError + Sized + Send + Sync + 'static {
   ///  let error = Error::new(Context, None);
   ///# }
   /// ```
-  fn new(context: Self::Context, source: Option<Box<dyn StdError + Send + Sync + 'static>>)
-    -> Self;
+  #[inline]
+  #[track_caller]
+  fn new(
+    context: Self::Context,
+    source: Option<Box<dyn StdError + Send + Sync + 'static>>,
+  ) -> Self {
+    Self::new_at(context, source, Location::caller())
+  }
+
+  /// Create a new error instance, like [new](EvitableError::new), but with
+  /// an explicit location instead of the caller location. Used by adapters
+  /// that create errors later than where they were set up, like
+  /// [FutureExt::context](crate::futures::FutureExt::context).
+  ///
+  /// # Arguments
+  ///
+  /// * `context` - Error context
+  /// * `source` - Optional error source
+  /// * `location` - Location the error is reported at
+  fn new_at(
+    context: Self::Context,
+    source: Option<Box<dyn StdError + Send + Sync + 'static>>,
+    location: &'static Location<'static>,
+  ) -> Self;
 
   /// Get the error kind.
   ///
@@ -156,6 +346,352 @@ pub trait EvitableError: StdError + Sized + Send + Sync + 'static {
   /// Get backtrace.
   fn backtrace(&self) -> &Backtrace;
 
+  /// Get the location in the source code where the error was created.
+  /// Unlike the [backtrace](EvitableError::backtrace), this is available
+  /// even when debug info has been stripped.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(description = "Error")]
+  ///  pub struct Context;
+  ///
+  ///  // Later
+  ///# fn main() {
+  ///  let error = Error::from_context(Context);
+  ///  assert_eq!(error.location().line(), line!() - 1);
+  ///# }
+  /// ```
+  fn location(&self) -> &'static Location<'static>;
+
+  /// Get the [tracing](https://docs.rs/tracing) spans that were active
+  /// when the error was created. Empty unless the `tracing` feature is
+  /// enabled. See [SpanTrace](SpanTrace).
+  fn span_trace(&self) -> &SpanTrace;
+
+  /// Record the error on the current [tracing](https://docs.rs/tracing)
+  /// span. The `error.kind`, `error.code` (if the error has a
+  /// [code](EvitableError::code)) and `error.message` fields are recorded
+  /// on the span, and an event with the same fields is emitted as a child
+  /// of the span. Spans only record fields they declare, so declare all
+  /// three as `tracing::field::Empty`. Requires the `tracing` feature.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(description = "Error")]
+  ///  pub struct Context;
+  ///
+  ///# fn main() {
+  ///  let span = tracing::info_span!(
+  ///    "load",
+  ///    error.kind = tracing::field::Empty,
+  ///    error.code = tracing::field::Empty,
+  ///    error.message = tracing::field::Empty,
+  ///  );
+  ///  let _guard = span.enter();
+  ///  Error::from_context(Context).record_in_current_span();
+  ///# }
+  /// ```
+  #[cfg(feature = "tracing")]
+  fn record_in_current_span(&self) {
+    let span = tracing::Span::current();
+    let kind = self.kind();
+    let code = self.code();
+    let message = self.context();
+    span.record("error.kind", tracing::field::display(&kind));
+    span.record("error.code", code);
+    span.record("error.message", tracing::field::display(message));
+
+    macro_rules! event {
+      ($level:expr) => {
+        tracing::event!(
+          parent: &span,
+          $level,
+          error.kind = %kind,
+          error.code = code,
+          error.message = %message,
+          "{}",
+          message
+        )
+      };
+    }
+
+    match self.severity() {
+      Severity::Trace => event!(tracing::Level::TRACE),
+      Severity::Debug => event!(tracing::Level::DEBUG),
+      Severity::Info => event!(tracing::Level::INFO),
+      Severity::Warn => event!(tracing::Level::WARN),
+      Severity::Error => event!(tracing::Level::ERROR),
+    }
+  }
+
+  /// Get the severity of the error. See [Severity](Severity).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(severity = "warn")]
+  ///  pub enum Context {
+  ///    #[evitable(description = "Not found", severity = "info")]
+  ///    NotFound,
+  ///
+  ///    #[evitable(description = "Timeout")]
+  ///    Timeout,
+  ///  }
+  ///
+  ///# fn main() {
+  ///  assert_eq!(Error::from_context(Context::NotFound).severity(), Severity::Info);
+  ///  assert_eq!(Error::from_context(Context::Timeout).severity(), Severity::Warn);
+  ///# }
+  /// ```
+  #[inline]
+  fn severity(&self) -> Severity {
+    self.context().severity()
+  }
+
+  /// Get the code of the error, set using `#[evitable(code = "...")]`.
+  #[inline]
+  fn code(&self) -> Option<&'static str> {
+    self.context().code()
+  }
+
+  /// Check if the error is marked with `#[evitable(retryable)]`, meaning
+  /// the operation that failed may succeed if retried. See
+  /// [retry](retry).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable]
+  ///  pub enum Context {
+  ///    #[evitable(description = "Timeout", retryable)]
+  ///    Timeout,
+  ///
+  ///    #[evitable(description = "Invalid input")]
+  ///    InvalidInput,
+  ///  }
+  ///
+  ///# fn main() {
+  ///  assert!(Error::from_context(Context::Timeout).is_retryable());
+  ///  assert!(!Error::from_context(Context::InvalidInput).is_retryable());
+  ///# }
+  /// ```
+  #[inline]
+  fn is_retryable(&self) -> bool {
+    self.context().is_retryable()
+  }
+
+  /// Get the delay before retrying, from the field marked with
+  /// `#[evitable(retry_after)]`, if any.
+  #[inline]
+  fn retry_after(&self) -> Option<Duration> {
+    self.context().retry_after()
+  }
+
+  /// Get the HTTP status of the error, set using
+  /// `#[evitable(http_status = 404)]`. Defaults to `500`. See [http](http).
+  #[inline]
+  fn http_status(&self) -> u16 {
+    self.context().http_status()
+  }
+
+  /// Get the gRPC status code of the error, set using
+  /// `#[evitable(grpc_code = "NotFound")]`. Defaults to
+  /// [Unknown](grpc::Code::Unknown). See [grpc](grpc).
+  #[inline]
+  fn grpc_code(&self) -> grpc::Code {
+    self.context().grpc_code()
+  }
+
+  /// Get the process exit code of the error, set using
+  /// `#[evitable(exit_code = 3)]`. Defaults to `1`. Never `0`, which is
+  /// reserved for success, so a failing `main` never exits successfully.
+  /// See [ExitResult](ExitResult).
+  #[inline]
+  fn exit_code(&self) -> u8 {
+    self.context().exit_code()
+  }
+
+  /// Get the [io::ErrorKind](std::io::ErrorKind) of the error, set using
+  /// `#[evitable(io_kind = NotFound)]`. Defaults to
+  /// [Other](std::io::ErrorKind::Other). This is the kind used when
+  /// converting the error into an [io::Error](std::io::Error).
+  #[inline]
+  fn io_kind(&self) -> io::ErrorKind {
+    self.context().io_kind()
+  }
+
+  /// Get the C ABI error code of the error, set using
+  /// `#[evitable(ffi_code = 2)]`. Defaults to `1`. Never `0` or `-1`,
+  /// which are reserved for success and panics.
+  #[inline]
+  fn ffi_code(&self) -> i32 {
+    self.context().ffi_code()
+  }
+
+  /// Render the error for internal use. Like the `Display` output, but
+  /// with the context rendered using
+  /// [ErrorContext::unredacted](ErrorContext::unredacted).
+  #[inline]
+  fn unredacted(&self) -> UnredactedError<'_, Self> {
+    UnredactedError::new(self)
+  }
+
+  /// Get the message of the error meant for end users. See
+  /// [ErrorContext::user_message](ErrorContext::user_message).
+  #[inline]
+  fn user_message(&self) -> UserMessage<'_, Self::Context> {
+    self.context().user_message()
+  }
+
+  /// Get the error wrapped by an [io::Error](std::io::Error), if it was
+  /// created from an error of this type.
+  ///
+  /// # Arguments
+  ///
+  /// * `error` - The IO error
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(description = "Not found", io_kind = NotFound)]
+  ///  pub struct Context;
+  ///
+  ///# fn main() {
+  ///  let io_error = std::io::Error::from(Error::from_context(Context));
+  ///  assert_eq!(io_error.kind(), std::io::ErrorKind::NotFound);
+  ///  assert!(Error::from_io(&io_error).is_some());
+  ///# }
+  /// ```
+  #[inline]
+  fn from_io(error: &io::Error) -> Option<&Self> {
+    error.get_ref()?.downcast_ref()
+  }
+
+  /// Log the error using the [log](https://docs.rs/log) crate, at the
+  /// level matching its [severity](EvitableError::severity). The kind and
+  /// code are added as structured `kind` and `code` fields. Requires the
+  /// `log` feature.
+  #[cfg(feature = "log")]
+  fn log(&self) {
+    ::log::log!(
+      target: "evitable",
+      self.severity().into(),
+      kind:% = self.kind(),
+      code = self.code();
+      "{}",
+      self
+    );
+  }
+
+  /// Get the attachments of this error. Typically, you want to use
+  /// [attachments](EvitableError::attachments) or
+  /// [request_ref](EvitableError::request_ref) instead.
+  fn attachment_store(&self) -> &Attachments;
+
+  /// Get the attachments of this error mutably. Typically, you want to use
+  /// [attach](EvitableError::attach) instead.
+  fn attachment_store_mut(&mut self) -> &mut Attachments;
+
+  /// Attach an arbitrary value to the error, like a request id or a
+  /// retry count. The value can later be retrieved using
+  /// [request_ref](EvitableError::request_ref), but it is not rendered
+  /// in reports. Use [attach_printable](EvitableError::attach_printable)
+  /// for that.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - Value to attach
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(description = "Error")]
+  ///  pub struct Context;
+  ///
+  ///  struct RequestId(u64);
+  ///
+  ///  // Later
+  ///# fn main() {
+  ///  let error = Error::from_context(Context).attach(RequestId(42));
+  ///  assert_eq!(error.request_ref::<RequestId>().unwrap().0, 42);
+  ///# }
+  /// ```
+  #[inline]
+  fn attach<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+    self.attachment_store_mut().push(value);
+    self
+  }
+
+  /// Attach a value to the error, which is rendered in reports.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - Value to attach
+  #[inline]
+  fn attach_printable<T: Display + Send + Sync + 'static>(mut self, value: T) -> Self {
+    self.attachment_store_mut().push_printable(value);
+    self
+  }
+
+  /// Get the frames added to this error using
+  /// [add_context](EvitableError::add_context).
+  fn frames(&self) -> &Frames;
+
+  /// Get the frames added to this error mutably. Typically, you want to use
+  /// [add_context](EvitableError::add_context) instead.
+  fn frames_mut(&mut self) -> &mut Frames;
+
+  /// Add context to the error without changing its type. The context
+  /// is pushed as a new [frame](Frame) (together with the caller location),
+  /// and is rendered after the error context in both `Display` and `Debug`
+  /// output. Any displayable value can be used, including other error
+  /// contexts.
+  ///
+  /// # Arguments
+  ///
+  /// * `frame` - Context to add
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(description = "Parse error")]
+  ///  pub struct Context;
+  ///
+  ///  // Later
+  ///# fn main() {
+  ///  let error = Error::from_context(Context).add_context("while loading plugin foo");
+  ///  assert_eq!(error.to_string(), "Parse error\n  while loading plugin foo");
+  ///# }
+  /// ```
+  #[inline]
+  #[track_caller]
+  fn add_context<D: Display + Send + Sync + 'static>(mut self, frame: D) -> Self {
+    self.frames_mut().push(frame);
+    self
+  }
+
+  /// Get all values of type `T` attached to the error, in the order
+  /// they were attached.
+  #[inline]
+  fn attachments<T: 'static>(&self) -> AttachmentsOf<'_, T> {
+    self.attachment_store().iter()
+  }
+
+  /// Get the most recently attached value of type `T`.
+  #[inline]
+  fn request_ref<T: 'static>(&self) -> Option<&T> {
+    self.attachment_store().get()
+  }
+
   /// Create a new error instance from an error context.
   ///
   /// # Arguments
@@ -175,11 +711,14 @@ pub trait EvitableError: StdError + Sized + Send + Sync + 'static {
   ///# }
   /// ```
   #[inline]
+  #[track_caller]
   fn from_context(context: Self::Context) -> Self {
     Self::new(context, None)
   }
 
   /// Create a new error instance from an error context and a source error.
+  /// The source can be any error, or anything else that can be converted
+  /// into a boxed error (like an [AnyError](AnyError) or a `String`).
   ///
   /// # Arguments
   ///
@@ -201,11 +740,29 @@ pub trait EvitableError: StdError + Sized + Send + Sync + 'static {
   ///# }
   /// ```
   #[inline]
-  fn from_error_context<S: StdError + Send + Sync + 'static>(
+  #[track_caller]
+  fn from_error_context<S: Into<Box<dyn StdError + Send + Sync + 'static>>>(
+    context: Self::Context,
+    error: S,
+  ) -> Self {
+    Self::new(context, Some(error.into()))
+  }
+
+  /// Create a new error instance from an error context and a source error,
+  /// with an explicit location. See [new_at](EvitableError::new_at).
+  ///
+  /// # Arguments
+  ///
+  /// * `context` - Error context
+  /// * `source` - Error source
+  /// * `location` - Location the error is reported at
+  #[inline]
+  fn from_error_context_at<S: Into<Box<dyn StdError + Send + Sync + 'static>>>(
     context: Self::Context,
     error: S,
+    location: &'static Location<'static>,
   ) -> Self {
-    Self::new(context, Some(Box::new(error)))
+    Self::new_at(context, Some(error.into()), location)
   }
 }
 
@@ -248,6 +805,154 @@ pub trait ErrorContext: Display + Debug + Sized + Send + Sync + 'static {
   /// ```
   fn kind(&self) -> Self::Kind;
 
+  /// Get the severity of the context. See [Severity](Severity).
+  #[inline]
+  fn severity(&self) -> Severity {
+    Severity::Error
+  }
+
+  /// Get the code of the context, set using `#[evitable(code = "...")]`.
+  #[inline]
+  fn code(&self) -> Option<&'static str> {
+    None
+  }
+
+  /// Check if the context is marked with `#[evitable(retryable)]`.
+  #[inline]
+  fn is_retryable(&self) -> bool {
+    false
+  }
+
+  /// Get the delay before retrying, from the field marked with
+  /// `#[evitable(retry_after)]`, if any.
+  #[inline]
+  fn retry_after(&self) -> Option<Duration> {
+    None
+  }
+
+  /// Get the HTTP status of the context, set using
+  /// `#[evitable(http_status = 404)]`. Defaults to `500`.
+  #[inline]
+  fn http_status(&self) -> u16 {
+    500
+  }
+
+  /// Get the gRPC status code of the context, set using
+  /// `#[evitable(grpc_code = "NotFound")]`. Defaults to
+  /// [Unknown](grpc::Code::Unknown).
+  #[inline]
+  fn grpc_code(&self) -> grpc::Code {
+    grpc::Code::Unknown
+  }
+
+  /// Get the process exit code of the context, set using
+  /// `#[evitable(exit_code = 3)]`. Defaults to `1`. Never `0`, which is
+  /// reserved for success.
+  #[inline]
+  fn exit_code(&self) -> u8 {
+    1
+  }
+
+  /// Get the [io::ErrorKind](std::io::ErrorKind) of the context, set using
+  /// `#[evitable(io_kind = NotFound)]`. Defaults to
+  /// [Other](std::io::ErrorKind::Other).
+  #[inline]
+  fn io_kind(&self) -> io::ErrorKind {
+    io::ErrorKind::Other
+  }
+
+  /// Get the C ABI error code of the context, set using
+  /// `#[evitable(ffi_code = 2)]`. Defaults to `1`. Never `0` or `-1`,
+  /// which are reserved for success and panics.
+  #[inline]
+  fn ffi_code(&self) -> i32 {
+    1
+  }
+
+  /// Format the context for internal use. Generated by `#[evitable]` for
+  /// contexts with fields marked with `#[evitable(redact)]`, showing the
+  /// fields that are referenced as `unredacted(field)` in the description.
+  /// Defaults to the `Display` output. See [unredacted](ErrorContext::unredacted).
+  #[inline]
+  fn fmt_unredacted(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    Display::fmt(self, f)
+  }
+
+  /// Render the context for internal use, like logs that are not exposed
+  /// to users. Fields marked with `#[evitable(redact)]` are hidden in all
+  /// other output (including `Debug`), unless they are referenced as
+  /// `unredacted(field)` in the description and rendered using this
+  /// method. `unredacted(field)` is the only supported syntax, it can't be
+  /// written inside the format string (like `{field:unredacted}`).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(description("Login failed for {} (hint: {})", user, unredacted(hint)))]
+  ///  pub struct Context {
+  ///    user: String,
+  ///    #[evitable(redact)]
+  ///    hint: String,
+  ///  }
+  ///
+  ///# fn main() {
+  ///  let context = Context { user: "admin".into(), hint: "pet name".into() };
+  ///  assert_eq!(context.to_string(), "Login failed for admin (hint: <redacted>)");
+  ///  assert_eq!(context.unredacted().to_string(), "Login failed for admin (hint: pet name)");
+  ///# }
+  /// ```
+  #[inline]
+  fn unredacted(&self) -> Unredacted<'_, Self> {
+    Unredacted::new(self)
+  }
+
+  /// Format the message of the context meant for end users. Generated by
+  /// `#[evitable]` for contexts with a user message. Defaults to
+  /// [DEFAULT_USER_MESSAGE](DEFAULT_USER_MESSAGE). See
+  /// [user_message](ErrorContext::user_message).
+  #[inline]
+  fn fmt_user_message(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(DEFAULT_USER_MESSAGE)
+  }
+
+  /// Render the message of the context meant for end users, set using
+  /// `#[evitable(user_message = "...")]`. Like the description, it can
+  /// reference fields using `user_message("Order {} was cancelled", id)`.
+  /// On enums, a user message on the type is used for variants without
+  /// one. Contexts without a user message fall back to
+  /// [DEFAULT_USER_MESSAGE](DEFAULT_USER_MESSAGE).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(user_message = "Something went wrong, please try again later")]
+  ///  pub enum Context {
+  ///    #[evitable(
+  ///      description("Card {} declined by gateway: {}", card, reason),
+  ///      user_message("Your card ending in {} was declined", card)
+  ///    )]
+  ///    Declined { card: u16, reason: String },
+  ///
+  ///    #[evitable(description = "Connection pool exhausted")]
+  ///    PoolExhausted,
+  ///  }
+  ///
+  ///# fn main() {
+  ///  let context = Context::Declined { card: 4242, reason: "do_not_honor".into() };
+  ///  assert_eq!(context.user_message().to_string(), "Your card ending in 4242 was declined");
+  ///  assert_eq!(
+  ///    Context::PoolExhausted.user_message().to_string(),
+  ///    "Something went wrong, please try again later"
+  ///  );
+  ///# }
+  /// ```
+  #[inline]
+  fn user_message(&self) -> UserMessage<'_, Self> {
+    UserMessage::new(self)
+  }
+
   /// Convert the current context into an error.
   ///
   /// # Arguments
@@ -269,7 +974,11 @@ pub trait ErrorContext: Display + Debug + Sized + Send + Sync + 'static {
   ///# }
   /// ```
   #[inline]
-  fn into_error<S: StdError + Send + Sync + 'static>(self, source: S) -> Self::Error {
+  #[track_caller]
+  fn into_error<S: Into<Box<dyn StdError + Send + Sync + 'static>>>(
+    self,
+    source: S,
+  ) -> Self::Error {
     Self::Error::from_error_context(self, source)
   }
 }
@@ -313,6 +1022,7 @@ pub trait OptionExt<T, C: ErrorContext> {
   ///  ok_option.unwrap();
   ///  ok_result.unwrap();
   ///# }
+  #[track_caller]
   fn context(self, f: impl FnOnce() -> C) -> Result<T, C::Error>;
 }
 
@@ -350,24 +1060,239 @@ pub trait ResultExt<T, E, C: ErrorContext>: OptionExt<T, C> {
   ///
   ///  ok_result.unwrap();
   ///# }
+  #[track_caller]
   fn context_with(self, f: impl FnOnce(&E) -> C) -> Result<T, C::Error>;
 }
 
+// These are written as explicit matches rather than using combinators,
+// as closures would swallow the caller location.
 impl<T, C: ErrorContext> OptionExt<T, C> for Option<T> {
+  #[track_caller]
   fn context(self, f: impl FnOnce() -> C) -> Result<T, C::Error> {
-    self.ok_or_else(|| C::Error::from_context(f()))
+    match self {
+      Some(v) => Ok(v),
+      None => Err(C::Error::from_context(f())),
+    }
   }
 }
 
 impl<T, E: StdError + Send + Sync + 'static, C: ErrorContext> OptionExt<T, C> for Result<T, E> {
+  #[track_caller]
   fn context(self, f: impl FnOnce() -> C) -> Result<T, C::Error> {
-    self.map_err(|e| C::Error::from_error_context(f(), e))
+    match self {
+      Ok(v) => Ok(v),
+      Err(e) => Err(C::Error::from_error_context(f(), e)),
+    }
   }
 }
 
 impl<T, E: StdError + Send + Sync + 'static, C: ErrorContext> ResultExt<T, E, C> for Result<T, E> {
+  #[track_caller]
   fn context_with(self, f: impl FnOnce(&E) -> C) -> Result<T, C::Error> {
-    self.map_err(|e| C::Error::from_error_context(f(&e), e))
+    match self {
+      Ok(v) => Ok(v),
+      Err(e) => Err(C::Error::from_error_context(f(&e), e)),
+    }
+  }
+}
+
+/// Extension trait for result types that already carry an evitable
+/// error, as opposed to [ResultExt](ResultExt), which converts errors
+/// into evitable errors.
+pub trait EvitableResultExt<T, E: EvitableError> {
+  /// Attach a value to the error, if any. The value is only created
+  /// in the error case.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - Attachment factory
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(description = "Error")]
+  ///  pub struct Context;
+  ///
+  ///  struct TenantId(&'static str);
+  ///
+  ///  fn load() -> Result<()> {
+  ///    Err(Context)?
+  ///  }
+  ///
+  ///# fn main() {
+  ///  let error = load().attach_with(|| TenantId("acme")).unwrap_err();
+  ///  assert_eq!(error.request_ref::<TenantId>().unwrap().0, "acme");
+  ///# }
+  /// ```
+  fn attach_with<A: Send + Sync + 'static>(self, f: impl FnOnce() -> A) -> Result<T, E>;
+
+  /// Attach a value to the error, if any, which is rendered in reports.
+  /// The value is only created in the error case.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - Attachment factory
+  fn attach_printable_with<A: Display + Send + Sync + 'static>(
+    self,
+    f: impl FnOnce() -> A,
+  ) -> Result<T, E>;
+
+  /// Add context to the error, if any, without changing the error type.
+  /// The context is only created in the error case. See
+  /// [EvitableError::add_context](EvitableError::add_context).
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - Context factory
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable(description = "Parse error")]
+  ///  pub struct Context;
+  ///
+  ///  fn parse() -> Result<()> {
+  ///    Err(Context)?
+  ///  }
+  ///
+  ///  fn load_plugin(name: &str) -> Result<()> {
+  ///    parse().add_context(|| format!("while loading plugin {}", name))
+  ///  }
+  ///
+  ///# fn main() {
+  ///  let error = load_plugin("foo").unwrap_err();
+  ///  assert_eq!(error.frames().len(), 1);
+  ///# }
+  /// ```
+  #[track_caller]
+  fn add_context<D: Display + Send + Sync + 'static>(self, f: impl FnOnce() -> D) -> Result<T, E>;
+
+  /// [Log](EvitableError::log) the error, if any, and return the result
+  /// unchanged. Requires the `log` feature.
+  #[cfg(feature = "log")]
+  fn log_err(self) -> Result<T, E>;
+}
+
+impl<T, E: EvitableError> EvitableResultExt<T, E> for Result<T, E> {
+  #[inline]
+  fn attach_with<A: Send + Sync + 'static>(self, f: impl FnOnce() -> A) -> Result<T, E> {
+    self.map_err(|e| e.attach(f()))
+  }
+
+  #[inline]
+  fn attach_printable_with<A: Display + Send + Sync + 'static>(
+    self,
+    f: impl FnOnce() -> A,
+  ) -> Result<T, E> {
+    self.map_err(|e| e.attach_printable(f()))
+  }
+
+  #[inline]
+  #[track_caller]
+  fn add_context<D: Display + Send + Sync + 'static>(self, f: impl FnOnce() -> D) -> Result<T, E> {
+    match self {
+      Ok(v) => Ok(v),
+      Err(e) => Err(e.add_context(f())),
+    }
+  }
+
+  #[cfg(feature = "log")]
+  #[inline]
+  fn log_err(self) -> Result<T, E> {
+    if let Err(e) = &self {
+      e.log();
+    }
+
+    self
+  }
+}
+
+/// Type erased error, used to funnel arbitrary errors into a context
+/// variant marked with `#[evitable(from_any)]`. Any error type can be
+/// converted into an `AnyError` using `?`. Note that `AnyError` does not
+/// implement [Error](std::error::Error) itself, as that would conflict
+/// with the blanket conversion.
+pub struct AnyError(Box<dyn StdError + Send + Sync + 'static>);
+
+impl AnyError {
+  /// Get a reference to the underlying error.
+  #[inline]
+  pub fn get_ref(&self) -> &(dyn StdError + Send + Sync + 'static) {
+    self.0.as_ref()
+  }
+
+  /// Consume the `AnyError`, returning the underlying error.
+  #[inline]
+  pub fn into_inner(self) -> Box<dyn StdError + Send + Sync + 'static> {
+    self.0
+  }
+}
+
+impl<E: StdError + Send + Sync + 'static> From<E> for AnyError {
+  #[inline]
+  fn from(error: E) -> Self {
+    AnyError(Box::new(error))
+  }
+}
+
+impl From<AnyError> for Box<dyn StdError + Send + Sync + 'static> {
+  #[inline]
+  fn from(error: AnyError) -> Self {
+    error.0
+  }
+}
+
+impl Display for AnyError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    Display::fmt(&self.0, f)
+  }
+}
+
+impl Debug for AnyError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    Debug::fmt(&self.0, f)
+  }
+}
+
+/// Extension trait for result types, for funneling any error into
+/// a context variant marked with `#[evitable(from_any)]`.
+pub trait AnyResultExt<T> {
+  /// Convert the error case into an [AnyError](AnyError), which can
+  /// be converted into any evitable error that has a `from_any` variant.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  ///# use evitable::*;
+  ///  #[evitable]
+  ///  pub enum Context {
+  ///    #[evitable(description = "Invalid input")]
+  ///    Invalid,
+  ///
+  ///    #[evitable(description = "Other error", from_any)]
+  ///    Other,
+  ///  }
+  ///
+  ///  fn parse(input: &str) -> Result<u8> {
+  ///    let value = input.parse::<u8>().or_other()?;
+  ///    Ok(value)
+  ///  }
+  ///
+  ///# fn main() {
+  ///  let error = parse("foo").unwrap_err();
+  ///  assert_eq!(error.kind(), evitable_context::ErrorKind::Other);
+  ///  assert!(error.source().is_some());
+  ///# }
+  /// ```
+  fn or_other(self) -> Result<T, AnyError>;
+}
+
+impl<T, E: StdError + Send + Sync + 'static> AnyResultExt<T> for Result<T, E> {
+  #[inline]
+  fn or_other(self) -> Result<T, AnyError> {
+    self.map_err(AnyError::from)
   }
 }
 
@@ -401,7 +1326,7 @@ impl<T, E: StdError + Send + Sync + 'static, C: ErrorContext> ResultExt<T, E, C>
 macro_rules! ensure {
   ($test:expr, $ctx:expr) => {
     if !($test) {
-      return Err($ctx.into());
+      return Err(::std::convert::From::from($ctx));
     }
   };
 }
@@ -433,6 +1358,105 @@ macro_rules! ensure {
 #[macro_export]
 macro_rules! fail {
   ($ctx:expr) => {
-    return Err($ctx.into());
+    return Err(::std::convert::From::from($ctx));
+  };
+}
+
+/// Utility macro to check several conditions, collecting all failures
+/// into [Errors](Errors) instead of returning at the first one. Evaluates
+/// to a `Result<(), Errors<_>>`. All contexts must produce the same
+/// error type.
+///
+/// # Example
+///
+/// ```rust
+///# use evitable::*;
+///  #[evitable]
+///  pub enum Context {
+///    #[evitable(description = "Name is empty")]
+///    EmptyName,
+///
+///    #[evitable(description("Age {} is out of range", 0))]
+///    InvalidAge(u8),
+///  }
+///
+///  fn validate_user(name: &str, age: u8) -> std::result::Result<(), Errors<Error>> {
+///    validate! {
+///      ensure!(!name.is_empty(), Context::EmptyName);
+///      ensure!(age < 150, Context::InvalidAge(age));
+///    }?;
+///
+///    Ok(())
+///  }
+///
+///# fn main() {
+///  validate_user("evi", 30).unwrap();
+///  assert_eq!(validate_user("", 200).unwrap_err().len(), 2);
+///# }
+/// ```
+#[macro_export]
+macro_rules! validate {
+  ($(ensure!($test:expr, $ctx:expr);)*) => {{
+    let mut errors = $crate::Errors::new();
+    $(
+      if !($test) {
+        errors.push($crate::__error_from_context($ctx));
+      }
+    )*
+
+    errors.into_result()
+  }};
+}
+
+/// Utility macro to inject failures for testing error paths. If the
+/// fail point `name` is activated (see [failpoints](failpoints)), the
+/// context returned by the factory is returned as an error, like with
+/// [fail](fail). Without the `failpoints` feature this compiles to
+/// nothing.
+///
+/// # Arguments
+///
+/// * `name` - Name of the fail point
+/// * `ctx` - Error context factory
+///
+/// # Example
+///
+/// ```rust
+///# use evitable::*;
+///  #[evitable(description = "Io error")]
+///  pub struct Context;
+///
+///  fn read_config() -> Result<String> {
+///    fail_point!("read_config", || Context);
+///    Ok("config".to_owned())
+///  }
+///
+///# fn main() {
+///  assert_eq!(read_config().unwrap(), "config");
+///# }
+/// ```
+#[cfg(feature = "failpoints")]
+#[macro_export]
+macro_rules! fail_point {
+  ($name:expr, $ctx:expr) => {
+    if $crate::failpoints::__eval($name) {
+      return Err(::std::convert::From::from(($ctx)()));
+    }
   };
 }
+
+/// Utility macro to inject failures for testing error paths. If the
+/// fail point `name` is activated (see `failpoints`), the
+/// context returned by the factory is returned as an error, like with
+/// [fail](fail). Without the `failpoints` feature this compiles to
+/// nothing.
+///
+/// # Arguments
+///
+/// * `name` - Name of the fail point
+/// * `ctx` - Error context factory
+#[cfg(not(feature = "failpoints"))]
+#[macro_export]
+macro_rules! fail_point {
+  ($name:expr, $ctx:expr) => {};
+}
@@ -0,0 +1,64 @@
+use super::*;
+use std::fmt;
+
+/// Message shown to users for contexts without a user message, set using
+/// `#[evitable(user_message = "...")]`.
+pub const DEFAULT_USER_MESSAGE: &str = "An unexpected error occurred";
+
+/// Selects which message of an error is shown by renderers, like
+/// [ErrorBody](crate::http::ErrorBody), [ProblemRenderer](crate::problem::ProblemRenderer)
+/// and [ExitResult](ExitResult).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Message {
+  /// The description of the context, written for developers.
+  Description,
+
+  /// The [user message](ErrorContext::user_message) of the context.
+  User,
+}
+
+impl Message {
+  /// Render the selected message of a context.
+  ///
+  /// # Arguments
+  ///
+  /// * `context` - The error context
+  pub fn render<C: ErrorContext>(self, context: &C) -> String {
+    match self {
+      Message::Description => context.to_string(),
+      Message::User => context.user_message().to_string(),
+    }
+  }
+}
+
+impl Default for Message {
+  #[inline]
+  fn default() -> Self {
+    Message::Description
+  }
+}
+
+/// Rendering of the message of an error context meant for end users.
+/// Created by [ErrorContext::user_message](ErrorContext::user_message).
+pub struct UserMessage<'a, C: ErrorContext>(&'a C);
+
+impl<'a, C: ErrorContext> UserMessage<'a, C> {
+  #[inline]
+  pub(crate) fn new(context: &'a C) -> Self {
+    UserMessage(context)
+  }
+}
+
+impl<'a, C: ErrorContext> Display for UserMessage<'a, C> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.0.fmt_user_message(f)
+  }
+}
+
+impl<'a, C: ErrorContext> Debug for UserMessage<'a, C> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.0.fmt_user_message(f)
+  }
+}
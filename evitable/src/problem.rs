@@ -0,0 +1,348 @@
+//! Rendering of errors as [RFC 7807](https://tools.ietf.org/html/rfc7807)
+//! problem details (`application/problem+json`). Requires the `serde`
+//! feature.
+//!
+//! The members of the problem document are derived from the error:
+//!
+//! * `type` - The name of the error kind inserted into a URI template,
+//!   which defaults to a relative reference like `#OutOfCredit` (see
+//!   [ProblemRenderer::type_template](ProblemRenderer::type_template)).
+//! * `title` - The name of the error kind, which is the same for every
+//!   occurrence of the problem.
+//! * `status` - The [HTTP status](crate::EvitableError::http_status) of the error.
+//! * `detail` - The description of the error context, followed by any
+//!   [frames](crate::EvitableError::add_context) added to the error.
+//! * Fields marked with `#[evitable(include_in_kind)]` are added as
+//!   extension members. Fields that don't implement `serde::Serialize`
+//!   are rendered using `Debug`, and fields marked with
+//!   `#[evitable(redact)]` are replaced with their replacement text.
+//!
+//! As with the [http](crate::http) module, the type, title, detail and
+//! extension members of internal errors (with a `5xx` status) are hidden,
+//! unless [set_expose_details](crate::http::set_expose_details) is enabled.
+//! The type of hidden problems is [BLANK_TYPE](BLANK_TYPE).
+//!
+//! Renderers can show the [user message](crate::ErrorContext::user_message)
+//! instead, see [ProblemRenderer::message](ProblemRenderer::message).
+//!
+//! # Example
+//!
+//! ```rust
+//! use evitable::*;
+//! use evitable::problem::ProblemRenderer;
+//!
+//! #[evitable]
+//! pub enum Context {
+//!   #[evitable(description("Out of credit, balance is {}", balance), http_status = 403)]
+//!   OutOfCredit {
+//!     #[evitable(include_in_kind)]
+//!     balance: u32,
+//!   },
+//! }
+//!
+//! # fn main() {
+//! let error = Error::from_context(Context::OutOfCredit { balance: 30 });
+//! let problem = ProblemRenderer::new()
+//!   .type_template("https://example.com/probs/{kind}")
+//!   .render(&error);
+//!
+//! assert_eq!(
+//!   String::from_utf8(problem.to_json()).unwrap(),
+//!   r#"{"type":"https://example.com/probs/OutOfCredit","title":"OutOfCredit","status":403,"detail":"Out of credit, balance is 30","balance":30}"#,
+//! );
+//! # }
+//! ```
+
+use super::*;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::fmt::Write;
+
+/// Media type of problem documents.
+pub const CONTENT_TYPE: &str = "application/problem+json";
+
+/// Template for the problem type used when no
+/// [type template](ProblemRenderer::type_template) is set.
+pub const DEFAULT_TYPE_TEMPLATE: &str = "#{kind}";
+
+/// Problem type used for internal errors whose details are hidden.
+pub const BLANK_TYPE: &str = "about:blank";
+
+const KIND_PLACEHOLDER: &str = "{kind}";
+
+/// Implemented for all error kinds generated by `#[evitable]`, providing
+/// the fields marked with `#[evitable(include_in_kind)]` as extension
+/// members.
+pub trait KindFields {
+  /// Get the fields of the kind as JSON values.
+  fn extensions(&self) -> Map<String, Value>;
+}
+
+/// An [RFC 7807](https://tools.ietf.org/html/rfc7807) problem document.
+/// Created using [Problem::new](Problem::new) or a
+/// [ProblemRenderer](ProblemRenderer).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+  type_uri: String,
+  title: String,
+  status: u16,
+  detail: Option<String>,
+  extensions: Map<String, Value>,
+}
+
+impl Problem {
+  /// Render an error using the default [ProblemRenderer](ProblemRenderer).
+  ///
+  /// # Arguments
+  ///
+  /// * `error` - The error
+  #[inline]
+  pub fn new<E>(error: &E) -> Self
+  where
+    E: EvitableError,
+    E::Kind: KindFields,
+  {
+    ProblemRenderer::new().render(error)
+  }
+
+  /// Get the `type` member.
+  #[inline]
+  pub fn type_uri(&self) -> &str {
+    &self.type_uri
+  }
+
+  /// Get the `title` member.
+  #[inline]
+  pub fn title(&self) -> &str {
+    &self.title
+  }
+
+  /// Get the `status` member.
+  #[inline]
+  pub fn status(&self) -> u16 {
+    self.status
+  }
+
+  /// Get the `detail` member.
+  #[inline]
+  pub fn detail(&self) -> Option<&str> {
+    self.detail.as_deref()
+  }
+
+  /// Get the extension members.
+  #[inline]
+  pub fn extensions(&self) -> &Map<String, Value> {
+    &self.extensions
+  }
+
+  /// Serialize the problem as JSON.
+  pub fn to_json(&self) -> Vec<u8> {
+    serde_json::to_vec(self).expect("Problem is always serializable")
+  }
+}
+
+impl Serialize for Problem {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(None)?;
+    map.serialize_entry("type", &self.type_uri)?;
+    map.serialize_entry("title", &self.title)?;
+    map.serialize_entry("status", &self.status)?;
+    if let Some(detail) = &self.detail {
+      map.serialize_entry("detail", detail)?;
+    }
+
+    for (key, value) in &self.extensions {
+      match key.as_str() {
+        "type" | "title" | "status" | "detail" | "instance" => (),
+        _ => map.serialize_entry(key, value)?,
+      }
+    }
+
+    map.end()
+  }
+}
+
+/// Configurable rendering of errors as [Problem](Problem)s.
+#[derive(Debug, Clone)]
+pub struct ProblemRenderer {
+  type_template: Cow<'static, str>,
+  message: Message,
+}
+
+impl Default for ProblemRenderer {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ProblemRenderer {
+  /// Create a renderer using [DEFAULT_TYPE_TEMPLATE](DEFAULT_TYPE_TEMPLATE)
+  /// as template for the `type`.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      type_template: Cow::Borrowed(DEFAULT_TYPE_TEMPLATE),
+      message: Message::Description,
+    }
+  }
+
+  /// Set the template for the `type` member. Every `{kind}` in the
+  /// template is replaced with the name of the error kind, like
+  /// `"https://example.com/errors/{kind}"`.
+  ///
+  /// # Arguments
+  ///
+  /// * `template` - The URI template
+  #[inline]
+  pub fn type_template(mut self, template: impl Into<Cow<'static, str>>) -> Self {
+    self.type_template = template.into();
+    self
+  }
+
+  /// Set which message of errors is rendered. With
+  /// [Message::User](crate::Message::User), the `detail` member is the user
+  /// message of the error (also for internal errors), without any frames.
+  /// The other members of internal errors are still hidden.
+  /// Defaults to [Message::Description](crate::Message::Description).
+  ///
+  /// # Arguments
+  ///
+  /// * `message` - The message to render
+  #[inline]
+  pub fn message(mut self, message: Message) -> Self {
+    self.message = message;
+    self
+  }
+
+  /// Render an error as a problem document.
+  ///
+  /// # Arguments
+  ///
+  /// * `error` - The error
+  pub fn render<E>(&self, error: &E) -> Problem
+  where
+    E: EvitableError,
+    E::Kind: KindFields,
+  {
+    let kind = error.kind();
+    let status = error.http_status();
+    let hidden = http::is_internal(status) && !http::expose_details();
+    let type_uri = if hidden {
+      BLANK_TYPE.to_owned()
+    } else {
+      self.type_template.replace(KIND_PLACEHOLDER, kind.name())
+    };
+    let title = if hidden {
+      http::INTERNAL_ERROR_MESSAGE
+    } else {
+      kind.name()
+    };
+    if self.message == Message::User {
+      return Problem {
+        type_uri,
+        title: title.to_owned(),
+        status,
+        detail: Some(error.user_message().to_string()),
+        extensions: if hidden {
+          Map::new()
+        } else {
+          kind.extensions()
+        },
+      };
+    }
+
+    if hidden {
+      return Problem {
+        type_uri,
+        title: title.to_owned(),
+        status,
+        detail: None,
+        extensions: Map::new(),
+      };
+    }
+
+    let mut detail = error.context().to_string();
+    for frame in error.frames() {
+      let _ = write!(detail, "\n  {}", frame);
+    }
+
+    Problem {
+      type_uri,
+      title: title.to_owned(),
+      status,
+      detail: Some(detail),
+      extensions: kind.extensions(),
+    }
+  }
+}
+
+#[doc(hidden)]
+pub mod __private {
+  use super::*;
+
+  pub use serde_json::{Map, Value};
+
+  pub struct Wrap<'a, T>(pub &'a T);
+
+  pub trait ViaSerialize {
+    fn __to_value(&self) -> Value;
+  }
+
+  impl<'a, T: Serialize> ViaSerialize for Wrap<'a, T> {
+    #[inline]
+    fn __to_value(&self) -> Value {
+      serde_json::to_value(self.0).unwrap_or(Value::Null)
+    }
+  }
+
+  pub trait ViaDebug {
+    fn __to_value(&self) -> Value;
+  }
+
+  impl<'a, T: Debug> ViaDebug for &Wrap<'a, T> {
+    #[inline]
+    fn __to_value(&self) -> Value {
+      Value::String(format!("{:?}", self.0))
+    }
+  }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_kind_fields {
+  ($kind:ident { $($variant:ident { $($member:tt => $binding:ident $(= $redacted:literal)?),* }),* }) => {
+    #[automatically_derived]
+    #[allow(unused_qualifications)]
+    impl $crate::problem::KindFields for $kind {
+      fn extensions(&self) -> $crate::problem::__private::Map<String, $crate::problem::__private::Value> {
+        #[allow(unused_imports)]
+        use $crate::problem::__private::{ViaDebug, ViaSerialize};
+
+        #[allow(unused_mut)]
+        let mut map = $crate::problem::__private::Map::new();
+        match self {
+          $($kind::$variant { $($member: $binding,)* .. } => {
+            $(map.insert(stringify!($member).to_owned(), $crate::__kind_field_value!($binding $(= $redacted)?));)*
+          })*
+          _ => (),
+        }
+
+        map
+      }
+    }
+  };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __kind_field_value {
+  ($binding:ident) => {
+    (&$crate::problem::__private::Wrap($binding)).__to_value()
+  };
+  ($binding:ident = $redacted:literal) => {{
+    let _ = $binding;
+    $crate::problem::__private::Value::from($redacted)
+  }};
+}
@@ -0,0 +1,76 @@
+use super::*;
+use std::fmt;
+
+/// Placeholder rendered in place of fields marked with
+/// `#[evitable(redact)]`.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct Redacted(&'static str);
+
+impl Redacted {
+  #[doc(hidden)]
+  #[inline]
+  pub fn __new<T: ?Sized>(_value: &T, replacement: &'static str) -> Self {
+    Redacted(replacement)
+  }
+}
+
+impl Display for Redacted {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.pad(self.0)
+  }
+}
+
+impl Debug for Redacted {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.pad(self.0)
+  }
+}
+
+/// Internal rendering of an error context, showing redacted fields
+/// marked with `unredacted(field)` in the description. Created by
+/// [ErrorContext::unredacted](ErrorContext::unredacted).
+pub struct Unredacted<'a, C: ErrorContext>(&'a C);
+
+impl<'a, C: ErrorContext> Unredacted<'a, C> {
+  #[inline]
+  pub(crate) fn new(context: &'a C) -> Self {
+    Unredacted(context)
+  }
+}
+
+impl<'a, C: ErrorContext> Display for Unredacted<'a, C> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.0.fmt_unredacted(f)
+  }
+}
+
+/// Internal rendering of an error, like its `Display` output, but with
+/// the context rendered using [Unredacted](Unredacted). Created by
+/// [EvitableError::unredacted](EvitableError::unredacted).
+pub struct UnredactedError<'a, E: EvitableError>(&'a E);
+
+impl<'a, E: EvitableError> UnredactedError<'a, E> {
+  #[inline]
+  pub(crate) fn new(error: &'a E) -> Self {
+    UnredactedError(error)
+  }
+}
+
+impl<'a, E: EvitableError> Display for UnredactedError<'a, E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    Display::fmt(&self.0.context().unredacted(), f)?;
+    for frame in self.0.frames() {
+      write!(f, "\n  {}", frame)?;
+    }
+    if let Some(source) = self.0.source() {
+      f.write_str("\n---- source ----\n")?;
+      Display::fmt(source, f)?;
+    }
+
+    Ok(())
+  }
+}
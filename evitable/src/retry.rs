@@ -0,0 +1,235 @@
+//! Retrying operations that fail with retryable errors. Variants are
+//! marked as retryable using `#[evitable(retryable)]` (or on the context,
+//! as a default for all variants), and a field can be marked using
+//! `#[evitable(retry_after)]` to override the delay before the next
+//! attempt.
+//!
+//! # Example
+//!
+//! ```rust
+//! use evitable::*;
+//! use evitable::retry::Backoff;
+//! use std::time::Duration;
+//!
+//! #[evitable]
+//! pub enum Context {
+//!   #[evitable(description = "Service unavailable", retryable)]
+//!   Unavailable,
+//!
+//!   #[evitable(description("Rate limited for {:?}", 0), retryable)]
+//!   RateLimited(#[evitable(retry_after)] Duration),
+//!
+//!   #[evitable(description = "Not found")]
+//!   NotFound,
+//! }
+//!
+//! # fn main() {
+//! let mut attempts = 0;
+//! let result = Backoff::new()
+//!   .initial_delay(Duration::from_millis(1))
+//!   .retry(|| {
+//!     attempts += 1;
+//!     match attempts {
+//!       1 => Err(Error::from_context(Context::Unavailable)),
+//!       2 => Err(Error::from_context(Context::RateLimited(Duration::from_millis(2)))),
+//!       _ => Ok(attempts),
+//!     }
+//!   });
+//!
+//! assert_eq!(result.unwrap(), 3);
+//! # }
+//! ```
+
+use super::*;
+use std::future::Future;
+use std::thread;
+use std::time::Duration;
+
+/// Conversion of a field marked with `#[evitable(retry_after)]` into
+/// the delay before the next attempt.
+pub trait RetryAfter {
+  /// Get the delay before the next attempt, if any.
+  fn retry_after(&self) -> Option<Duration>;
+}
+
+impl RetryAfter for Duration {
+  #[inline]
+  fn retry_after(&self) -> Option<Duration> {
+    Some(*self)
+  }
+}
+
+impl<T: RetryAfter> RetryAfter for Option<T> {
+  #[inline]
+  fn retry_after(&self) -> Option<Duration> {
+    self.as_ref().and_then(RetryAfter::retry_after)
+  }
+}
+
+/// Exponential backoff policy for retrying operations. Operations are
+/// retried only while they fail with [retryable](EvitableError::is_retryable)
+/// errors, and at most [max_attempts](Backoff::max_attempts) times in total.
+/// The delay before each new attempt starts at
+/// [initial_delay](Backoff::initial_delay), and is multiplied by the
+/// [multiplier](Backoff::multiplier) after each attempt, up to
+/// [max_delay](Backoff::max_delay). If the error reports a
+/// [retry_after](EvitableError::retry_after) delay, it's used instead.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+  max_attempts: u32,
+  initial_delay: Duration,
+  max_delay: Duration,
+  multiplier: u32,
+}
+
+impl Default for Backoff {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Backoff {
+  /// Create a backoff policy with 3 attempts, starting at a delay of
+  /// 100 milliseconds, doubled after each attempt, and at most 10 seconds.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      max_attempts: 3,
+      initial_delay: Duration::from_millis(100),
+      max_delay: Duration::from_secs(10),
+      multiplier: 2,
+    }
+  }
+
+  /// Set the maximum number of attempts, including the first one.
+  #[inline]
+  pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+    self.max_attempts = max_attempts;
+    self
+  }
+
+  /// Set the delay before the second attempt.
+  #[inline]
+  pub fn initial_delay(mut self, delay: Duration) -> Self {
+    self.initial_delay = delay;
+    self
+  }
+
+  /// Set the maximum delay between attempts.
+  #[inline]
+  pub fn max_delay(mut self, delay: Duration) -> Self {
+    self.max_delay = delay;
+    self
+  }
+
+  /// Set the factor the delay is multiplied with after each attempt.
+  #[inline]
+  pub fn multiplier(mut self, multiplier: u32) -> Self {
+    self.multiplier = multiplier;
+    self
+  }
+
+  /// Get the delay before the attempt following `attempt` (starting at 1),
+  /// or `None` if the error should not be retried.
+  fn next_delay<E: EvitableError>(&self, attempt: u32, error: &E) -> Option<Duration> {
+    if attempt >= self.max_attempts || !error.is_retryable() {
+      return None;
+    }
+
+    let delay = error.retry_after().unwrap_or_else(|| {
+      let factor = self.multiplier.saturating_pow(attempt - 1);
+      self
+        .initial_delay
+        .checked_mul(factor)
+        .map_or(self.max_delay, |d| d.min(self.max_delay))
+    });
+
+    Some(delay)
+  }
+
+  /// Run `op`, retrying it while it fails with a retryable error. The
+  /// current thread is put to sleep between attempts. Returns the result
+  /// of the last attempt.
+  ///
+  /// # Arguments
+  ///
+  /// * `op` - The operation to run
+  pub fn retry<T, E: EvitableError>(&self, mut op: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+      match op() {
+        Ok(v) => return Ok(v),
+        Err(e) => match self.next_delay(attempt, &e) {
+          None => return Err(e),
+          Some(delay) => thread::sleep(delay),
+        },
+      }
+
+      attempt += 1;
+    }
+  }
+
+  /// Run the future returned by `op`, retrying it while it fails with a
+  /// retryable error. Between attempts, the future returned by `sleep` is
+  /// awaited, which allows using the timer of any async runtime (like
+  /// `tokio::time::sleep`). Returns the result of the last attempt.
+  ///
+  /// # Arguments
+  ///
+  /// * `op` - The operation to run
+  /// * `sleep` - Factory for futures completing after the given delay
+  pub async fn retry_async<T, E, Fut, S>(
+    &self,
+    mut op: impl FnMut() -> Fut,
+    mut sleep: impl FnMut(Duration) -> S,
+  ) -> Result<T, E>
+  where
+    E: EvitableError,
+    Fut: Future<Output = Result<T, E>>,
+    S: Future<Output = ()>,
+  {
+    let mut attempt = 1;
+    loop {
+      match op().await {
+        Ok(v) => return Ok(v),
+        Err(e) => match self.next_delay(attempt, &e) {
+          None => return Err(e),
+          Some(delay) => sleep(delay).await,
+        },
+      }
+
+      attempt += 1;
+    }
+  }
+}
+
+/// Run `op` using the default [Backoff](Backoff) policy. See
+/// [Backoff::retry](Backoff::retry).
+///
+/// # Arguments
+///
+/// * `op` - The operation to run
+#[inline]
+pub fn retry<T, E: EvitableError>(op: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+  Backoff::new().retry(op)
+}
+
+/// Run the future returned by `op` using the default [Backoff](Backoff)
+/// policy. See [Backoff::retry_async](Backoff::retry_async).
+///
+/// # Arguments
+///
+/// * `op` - The operation to run
+/// * `sleep` - Factory for futures completing after the given delay
+pub async fn retry_async<T, E, Fut, S>(
+  op: impl FnMut() -> Fut,
+  sleep: impl FnMut(Duration) -> S,
+) -> Result<T, E>
+where
+  E: EvitableError,
+  Fut: Future<Output = Result<T, E>>,
+  S: Future<Output = ()>,
+{
+  Backoff::new().retry_async(op, sleep).await
+}
@@ -0,0 +1,64 @@
+use std::fmt::{self, Display};
+
+/// How severe an error is, from routine ([Trace](Severity::Trace)) to
+/// critical ([Error](Severity::Error)). Set using
+/// `#[evitable(severity = "warn")]` on the context, or on individual
+/// variants. Errors are [Error](Severity::Error) by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Severity {
+  /// Very low priority, often extremely verbose information.
+  Trace,
+
+  /// Lower priority information.
+  Debug,
+
+  /// Routine errors, like a missing entry.
+  Info,
+
+  /// Errors that may need attention.
+  Warn,
+
+  /// Errors that need attention.
+  #[default]
+  Error,
+}
+
+impl Display for Severity {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Severity::Trace => "trace",
+      Severity::Debug => "debug",
+      Severity::Info => "info",
+      Severity::Warn => "warn",
+      Severity::Error => "error",
+    })
+  }
+}
+
+#[cfg(feature = "log")]
+impl From<Severity> for log::Level {
+  #[inline]
+  fn from(severity: Severity) -> Self {
+    match severity {
+      Severity::Trace => log::Level::Trace,
+      Severity::Debug => log::Level::Debug,
+      Severity::Info => log::Level::Info,
+      Severity::Warn => log::Level::Warn,
+      Severity::Error => log::Level::Error,
+    }
+  }
+}
+
+#[cfg(feature = "tracing")]
+impl From<Severity> for tracing::Level {
+  #[inline]
+  fn from(severity: Severity) -> Self {
+    match severity {
+      Severity::Trace => tracing::Level::TRACE,
+      Severity::Debug => tracing::Level::DEBUG,
+      Severity::Info => tracing::Level::INFO,
+      Severity::Warn => tracing::Level::WARN,
+      Severity::Error => tracing::Level::ERROR,
+    }
+  }
+}
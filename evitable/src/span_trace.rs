@@ -0,0 +1,71 @@
+use std::fmt::{self, Debug, Display};
+
+/// The [tracing](https://docs.rs/tracing) spans that were active when an
+/// error was created. Every error generated by `#[evitable]` captures one.
+/// Spans are only captured if the `tracing` feature is enabled, and a
+/// [tracing_error::ErrorLayer](https://docs.rs/tracing-error) is installed
+/// in the current subscriber; otherwise the span trace is empty.
+#[derive(Default)]
+pub struct SpanTrace {
+  #[cfg(feature = "tracing")]
+  inner: Option<Box<tracing_error::SpanTrace>>,
+}
+
+impl SpanTrace {
+  /// Capture the currently active spans.
+  #[inline]
+  pub fn capture() -> Self {
+    #[cfg(feature = "tracing")]
+    {
+      let trace = tracing_error::SpanTrace::capture();
+      let inner = if trace.status() == tracing_error::SpanTraceStatus::CAPTURED {
+        Some(Box::new(trace))
+      } else {
+        None
+      };
+
+      SpanTrace { inner }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    SpanTrace {}
+  }
+
+  /// Check if no spans were captured.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.get().is_none()
+  }
+
+  /// Get the captured [tracing_error::SpanTrace](https://docs.rs/tracing-error),
+  /// if any spans were captured.
+  #[cfg(feature = "tracing")]
+  #[inline]
+  pub fn get(&self) -> Option<&tracing_error::SpanTrace> {
+    self.inner.as_deref()
+  }
+
+  #[cfg(not(feature = "tracing"))]
+  #[inline]
+  fn get(&self) -> Option<&dyn Display> {
+    None
+  }
+}
+
+impl Display for SpanTrace {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.get() {
+      None => Ok(()),
+      Some(trace) => Display::fmt(trace, f),
+    }
+  }
+}
+
+impl Debug for SpanTrace {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.get() {
+      None => f.write_str("SpanTrace(<empty>)"),
+      Some(trace) => Display::fmt(trace, f),
+    }
+  }
+}
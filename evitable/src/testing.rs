@@ -0,0 +1,164 @@
+//! Assertion macros for testing error paths. Failure messages include
+//! the full rendered error chain (the `Debug` output of the error).
+//!
+//! The macros are exported from the crate root as well as from this
+//! module.
+
+use super::*;
+
+#[doc(inline)]
+pub use crate::{assert_err_matches, assert_kind, assert_source};
+
+/// Assert that a result is an error of a given kind.
+///
+/// # Arguments
+///
+/// * `result` - A result with an evitable error
+/// * `kind` - The expected error kind
+///
+/// # Example
+///
+/// ```rust
+///# use evitable::*;
+///# use evitable::testing::assert_kind;
+///  #[evitable]
+///  pub enum Context {
+///    #[evitable(description = "Io error")]
+///    Io,
+///  }
+///
+///# fn main() {
+///  let result: Result<()> = Err(Context::Io.into());
+///  assert_kind!(result, ErrorKind::Io);
+///# }
+/// ```
+#[macro_export]
+macro_rules! assert_kind {
+  ($result:expr, $kind:expr $(,)?) => {
+    $crate::testing::__assert_kind(&$result, $kind, stringify!($kind))
+  };
+}
+
+/// Assert that a result is an error with a context matching a pattern,
+/// with an optional guard. Bindings in the pattern are references into
+/// the error context.
+///
+/// # Arguments
+///
+/// * `result` - A result with an evitable error
+/// * `pattern` - Pattern for the error context, optionally followed by `if guard`
+///
+/// # Example
+///
+/// ```rust
+///# use evitable::*;
+///# use evitable::testing::assert_err_matches;
+///  #[evitable]
+///  pub enum Context {
+///    #[evitable(description("Expected {}", expected))]
+///    InvalidToken { expected: String, actual: String },
+///  }
+///
+///# fn main() {
+///  let result: Result<()> = Err(Context::InvalidToken {
+///    expected: "EOF".to_owned(),
+///    actual: "let".to_owned(),
+///  }.into());
+///  assert_err_matches!(result, Context::InvalidToken { expected, .. } if expected == "EOF");
+///# }
+/// ```
+#[macro_export]
+macro_rules! assert_err_matches {
+  ($result:expr, $($pattern:pat)|+ $(if $guard:expr)? $(,)?) => {
+    match &$result {
+      ::std::result::Result::Ok(_) => panic!(
+        "assertion failed: expected an error matching `{}`, got `Ok`",
+        stringify!($($pattern)|+ $(if $guard)?),
+      ),
+      ::std::result::Result::Err(err) => match $crate::EvitableError::context(err) {
+        $($pattern)|+ $(if $guard)? => (),
+        _ => panic!(
+          "assertion failed: expected an error matching `{}`, got:\n{:?}",
+          stringify!($($pattern)|+ $(if $guard)?),
+          err,
+        ),
+      },
+    }
+  };
+}
+
+/// Assert that an error has a source of a given type, anywhere in its
+/// chain of sources. Evaluates to a reference to the source.
+///
+/// # Arguments
+///
+/// * `err` - An error
+/// * `ty` - The expected type of the source
+///
+/// # Example
+///
+/// ```rust
+///# use evitable::*;
+///# use evitable::testing::assert_source;
+///# use std::io;
+///  #[evitable(description = "Io error", from = io::Error)]
+///  pub struct Context;
+///
+///# fn main() {
+///  let err = Error::from(io::Error::from(io::ErrorKind::NotFound));
+///  let source = assert_source!(err, io::Error);
+///  assert_eq!(source.kind(), io::ErrorKind::NotFound);
+///# }
+/// ```
+#[macro_export]
+macro_rules! assert_source {
+  ($err:expr, $ty:ty $(,)?) => {
+    $crate::testing::__assert_source::<$ty, _>(&$err, stringify!($ty))
+  };
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_kind<T, E>(result: &Result<T, E>, kind: E::Kind, kind_str: &str)
+where
+  E: EvitableError,
+  E::Kind: Debug,
+{
+  match result {
+    Ok(_) => panic!(
+      "assertion failed: expected an error of kind `{}`, got `Ok`",
+      kind_str
+    ),
+    Err(err) => {
+      let actual = err.kind();
+      if actual != kind {
+        panic!(
+          "assertion failed: expected an error of kind `{}`, got `{:?}`:\n{:?}",
+          kind_str, actual, err
+        );
+      }
+    }
+  }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_source<'a, T, E>(err: &'a E, ty_str: &str) -> &'a T
+where
+  T: StdError + 'static,
+  E: StdError + 'static,
+{
+  let mut current = err.source();
+  while let Some(source) = current {
+    if let Some(source) = source.downcast_ref::<T>() {
+      return source;
+    }
+
+    current = source.source();
+  }
+
+  panic!(
+    "assertion failed: expected a source of type `{}`, got:\n{:?}",
+    ty_str, err
+  )
+}
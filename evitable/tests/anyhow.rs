@@ -0,0 +1,72 @@
+#![cfg(feature = "anyhow")]
+
+extern crate evitable;
+
+use evitable::*;
+
+#[evitable]
+pub enum Context {
+  #[evitable(description("Invalid port: {}", 0))]
+  InvalidPort(u16),
+
+  #[evitable(description = "Upstream failed", from = anyhow::Error)]
+  Upstream,
+}
+
+mod other {
+  use super::*;
+
+  #[evitable(description = "Other")]
+  pub struct Context;
+}
+
+fn parse(port: u16) -> Result<u16> {
+  ensure!(port != 0, Context::InvalidPort(port));
+  Ok(port)
+}
+
+fn run(port: u16) -> anyhow::Result<u16> {
+  Ok(parse(port)?)
+}
+
+fn call_upstream() -> Result<()> {
+  Err(anyhow::anyhow!("connection refused"))?
+}
+
+#[test]
+fn downcast() {
+  let error = run(0).unwrap_err();
+  let inner = error.downcast_ref::<Error>().unwrap();
+  assert_eq!(inner.kind(), ErrorKind::InvalidPort);
+  assert!(error.downcast_ref::<other::Error>().is_none());
+}
+
+#[test]
+fn evitable_kind() {
+  let error = run(0).unwrap_err();
+  assert_eq!(
+    error.evitable_kind::<ErrorKind>(),
+    Some(ErrorKind::InvalidPort)
+  );
+  assert_eq!(error.evitable_kind::<other::ErrorKind>(), None);
+
+  let error = error.context("while starting");
+  assert_eq!(
+    error.evitable_kind::<ErrorKind>(),
+    Some(ErrorKind::InvalidPort)
+  );
+  assert_eq!(
+    error.evitable_error::<Error>().unwrap().to_string(),
+    "Invalid port: 0"
+  );
+  assert!(anyhow::anyhow!("plain")
+    .evitable_kind::<ErrorKind>()
+    .is_none());
+}
+
+#[test]
+fn anyhow_source() {
+  let error = call_upstream().unwrap_err();
+  assert_eq!(error.kind(), ErrorKind::Upstream);
+  assert_eq!(error.source().unwrap().to_string(), "connection refused");
+}
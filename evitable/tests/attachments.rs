@@ -0,0 +1,55 @@
+extern crate evitable;
+
+use evitable::*;
+
+#[evitable(description = "Error")]
+pub struct Context;
+
+#[derive(Debug, PartialEq)]
+struct RequestId(u64);
+
+#[derive(Debug, PartialEq)]
+struct RetryCount(u8);
+
+fn fail() -> Result<()> {
+  Err(Context)?
+}
+
+#[test]
+fn attach_and_request() {
+  let err = Error::from(Context)
+    .attach(RequestId(1))
+    .attach(RetryCount(3))
+    .attach(RequestId(2));
+
+  assert_eq!(err.request_ref::<RequestId>(), Some(&RequestId(2)));
+  assert_eq!(err.request_ref::<RetryCount>(), Some(&RetryCount(3)));
+  assert_eq!(err.request_ref::<String>(), None);
+  assert_eq!(
+    err.attachments::<RequestId>().collect::<Vec<_>>(),
+    vec![&RequestId(1), &RequestId(2)]
+  );
+}
+
+#[test]
+fn attach_with() {
+  let err = fail().attach_with(|| RequestId(42)).unwrap_err();
+  assert_eq!(err.request_ref::<RequestId>(), Some(&RequestId(42)));
+
+  let ok: Result<()> = Ok(());
+  ok.attach_with(|| -> RequestId { unimplemented!() })
+    .unwrap();
+}
+
+#[test]
+fn printable_attachments_in_report() {
+  let err = fail()
+    .attach_printable_with(|| "tenant: acme")
+    .attach_with(|| RequestId(42))
+    .unwrap_err();
+
+  let report = format!("{:?}", err);
+  assert!(report.contains("tenant: acme"));
+  assert!(!report.contains("42"));
+  assert!(!format!("{}", err).contains("tenant: acme"));
+}
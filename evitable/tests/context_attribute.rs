@@ -0,0 +1,197 @@
+extern crate evitable;
+
+use evitable::*;
+use std::io;
+
+#[evitable(description("Failed to load {}", path))]
+pub struct LoadContext {
+  path: String,
+}
+
+fn read(path: &str) -> io::Result<String> {
+  match path {
+    "missing" => Err(io::Error::from(io::ErrorKind::NotFound)),
+    _ => Ok(path.to_owned()),
+  }
+}
+
+#[evitable::with_context(LoadContext { path: path.to_owned() })]
+fn load(path: &str) -> LoadResult<usize> {
+  let content = read(path)?;
+  let len = content.len();
+  if len > 10 {
+    return Ok(10);
+  }
+
+  let value: usize = "4".parse()?;
+  Ok(len + value)
+}
+
+#[test]
+fn ok() {
+  assert_eq!(load("ok").unwrap(), 6);
+  assert_eq!(load("a long file name").unwrap(), 10);
+}
+
+#[test]
+fn wraps_error() {
+  let err = load("missing").unwrap_err();
+  assert_eq!(err.context().path, "missing");
+  assert_eq!(
+    err.to_string().lines().next(),
+    Some("Failed to load missing")
+  );
+  assert!(err.source().unwrap().downcast_ref::<io::Error>().is_some());
+}
+
+fn consume(path: String) -> io::Result<String> {
+  read(&path)
+}
+
+#[with_context(move LoadContext { path: path.clone() })]
+fn load_owned(path: String) -> LoadResult<String> {
+  let content = consume(path)?;
+  Ok(content)
+}
+
+#[test]
+fn body_takes_ownership() {
+  assert_eq!(load_owned("ok".to_owned()).unwrap(), "ok");
+  assert_eq!(
+    load_owned("missing".to_owned()).unwrap_err().context().path,
+    "missing"
+  );
+}
+
+struct Loader {
+  prefix: &'static str,
+}
+
+impl Loader {
+  #[with_context(LoadContext { path: format!("{}/{}", self.prefix, name) })]
+  fn load(&self, name: &str) -> std::result::Result<String, LoadError> {
+    let content = read(name)?;
+    Ok(format!("{}/{}", self.prefix, content))
+  }
+}
+
+#[test]
+fn method() {
+  let loader = Loader { prefix: "root" };
+  assert_eq!(loader.load("ok").unwrap(), "root/ok");
+  assert_eq!(
+    loader.load("missing").unwrap_err().context().path,
+    "root/missing"
+  );
+}
+
+#[with_context(LoadContext { path: path.to_owned() })]
+async fn load_async(path: &str) -> LoadResult<String> {
+  let content = async { read(path) }.await?;
+  Ok(content)
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+  use std::task::{Context, Poll, Waker};
+
+  let mut future = Box::pin(future);
+  let mut cx = Context::from_waker(Waker::noop());
+  loop {
+    if let Poll::Ready(v) = future.as_mut().poll(&mut cx) {
+      return v;
+    }
+  }
+}
+
+#[test]
+fn async_fn() {
+  assert_eq!(block_on(load_async("ok")).unwrap(), "ok");
+  assert_eq!(
+    block_on(load_async("missing")).unwrap_err().context().path,
+    "missing"
+  );
+}
+
+#[evitable]
+pub enum CheckContext {
+  #[evitable(description("Value {} is too large", value))]
+  TooLarge { value: u32 },
+
+  #[evitable(description = "Value is odd")]
+  Odd,
+
+  #[evitable(description = "Value is zero")]
+  Zero,
+
+  #[evitable(description("Failed to check {}", name))]
+  Check { name: String },
+}
+
+#[with_context(CheckContext::Check { name: name.to_owned() })]
+fn check(name: &str, value: u32) -> CheckResult<u32> {
+  ensure!(value < 10, CheckContext::TooLarge { value });
+  if value == 0 {
+    return Err(CheckError::from_context(CheckContext::Zero));
+  }
+  if value % 2 == 1 {
+    fail!(CheckContext::Odd);
+  }
+
+  let parsed: u32 = name.parse()?;
+  Ok(parsed + value)
+}
+
+#[with_context(CheckContext::Check { name: name.to_owned() })]
+async fn check_async(name: &str, value: u32) -> CheckResult<u32> {
+  ensure!(value < 10, CheckContext::TooLarge { value });
+  if value == 0 {
+    return Err(CheckError::from_context(CheckContext::Zero));
+  }
+  if value % 2 == 1 {
+    fail!(CheckContext::Odd);
+  }
+
+  let parsed: u32 = async { name.parse() }.await?;
+  Ok(parsed + value)
+}
+
+fn source_kind(error: &CheckError) -> Option<check::ErrorKind> {
+  error
+    .source()
+    .and_then(|source| source.downcast_ref::<CheckError>())
+    .map(|source| source.kind())
+}
+
+fn assert_check(result: impl Fn(&str, u32) -> CheckResult<u32>) {
+  use check::ErrorKind;
+
+  assert_eq!(result("1", 2).unwrap(), 3);
+
+  for (value, kind) in [
+    (20, ErrorKind::TooLarge),
+    (0, ErrorKind::Zero),
+    (3, ErrorKind::Odd),
+  ] {
+    let err = result("1", value).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Check);
+    assert_eq!(source_kind(&err), Some(kind));
+  }
+
+  let err = result("one", 2).unwrap_err();
+  assert_eq!(err.kind(), ErrorKind::Check);
+  assert!(err
+    .source()
+    .unwrap()
+    .downcast_ref::<std::num::ParseIntError>()
+    .is_some());
+}
+
+#[test]
+fn early_returns() {
+  assert_check(check);
+}
+
+#[test]
+fn early_returns_async() {
+  assert_check(|name, value| block_on(check_async(name, value)));
+}
@@ -0,0 +1,87 @@
+extern crate evitable;
+
+use evitable::*;
+
+#[evitable]
+pub enum Context {
+  #[evitable(description("Negative value: {}", 0))]
+  Negative(i32),
+
+  #[evitable(description = "Too large")]
+  TooLarge,
+}
+
+fn check(value: i32) -> Result<i32> {
+  ensure!(value >= 0, Context::Negative(value));
+  ensure!(value < 100, Context::TooLarge);
+
+  Ok(value)
+}
+
+#[test]
+fn collect_errors_ok() {
+  let values = vec![1, 2, 3].into_iter().map(check).collect_errors();
+  assert_eq!(values.unwrap(), vec![1, 2, 3]);
+}
+
+#[test]
+fn collect_errors_err() {
+  let errors = vec![1, -2, 300, -4]
+    .into_iter()
+    .map(check)
+    .collect_errors()
+    .unwrap_err();
+
+  let kinds: Vec<_> = errors.iter().map(|e| e.kind()).collect();
+  assert_eq!(
+    kinds,
+    vec![
+      evitable_context::ErrorKind::Negative,
+      evitable_context::ErrorKind::TooLarge,
+      evitable_context::ErrorKind::Negative,
+    ]
+  );
+  assert!(errors.source().is_none());
+}
+
+#[test]
+fn partition_results() {
+  let (values, errors) = vec![1, -2, 3].into_iter().map(check).partition_results();
+  assert_eq!(values, vec![1, 3]);
+  assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn validate() {
+  fn validate_range(min: i32, max: i32) -> std::result::Result<(), Errors<Error>> {
+    validate! {
+      ensure!(min >= 0, Context::Negative(min));
+      ensure!(max >= 0, Context::Negative(max));
+      ensure!(max < 100, Context::TooLarge);
+    }
+  }
+
+  validate_range(1, 2).unwrap();
+
+  let errors = validate_range(-1, -2).unwrap_err();
+  assert_eq!(errors.len(), 2);
+  assert_eq!(
+    errors.to_string(),
+    "2 errors occurred:\n  - Negative value: -1\n  - Negative value: -2"
+  );
+}
+
+#[test]
+fn display_renders_every_error() {
+  let mut errors = Errors::new();
+  errors.push(Error::from_context(Context::Negative(-1)).add_context("in row 1"));
+  errors.push(Error::from_context(Context::TooLarge));
+  assert_eq!(
+    errors.to_string(),
+    "2 errors occurred:\n  - Negative value: -1\n      in row 1\n  - Too large"
+  );
+
+  let single: Errors<Error> = Error::from_context(Context::TooLarge).into();
+  assert_eq!(single.to_string(), "Too large");
+  assert!(single.source().is_none());
+}
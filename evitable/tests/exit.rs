@@ -0,0 +1,52 @@
+extern crate evitable;
+
+use evitable::*;
+use std::process::{ExitCode, Termination};
+
+#[evitable(exit_code = 2)]
+pub enum Context {
+  #[evitable(description = "Config not found", exit_code = 3)]
+  ConfigNotFound,
+
+  #[evitable(description = "Invalid arguments")]
+  InvalidArguments,
+}
+
+mod unmapped {
+  use super::*;
+
+  #[evitable(description = "Something broke")]
+  pub struct Context;
+}
+
+fn run(fail: Option<Context>) -> Result<()> {
+  match fail {
+    None => Ok(()),
+    Some(context) => Err(context)?,
+  }
+}
+
+#[test]
+fn exit_code() {
+  assert_eq!(Context::ConfigNotFound.exit_code(), 3);
+  assert_eq!(ErrorKind::InvalidArguments.exit_code(), 2);
+  assert_eq!(Error::from_context(Context::ConfigNotFound).exit_code(), 3);
+  assert_eq!(
+    unmapped::Error::from_context(unmapped::Context).exit_code(),
+    1
+  );
+}
+
+#[test]
+fn exit_result() {
+  let result: ExitResult<Error> = run(None).into();
+  assert_eq!(result.exit_code(), 0);
+  assert_eq!(result.report(), ExitCode::SUCCESS);
+
+  let result: ExitResult<Error> = run(Some(Context::ConfigNotFound)).into();
+  assert_eq!(result.exit_code(), 3);
+  assert_eq!(result.report(), ExitCode::from(3));
+
+  let result = ExitResult::from(unmapped::Error::from_context(unmapped::Context));
+  assert_eq!(result.report(), ExitCode::FAILURE);
+}
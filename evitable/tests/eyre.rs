@@ -0,0 +1,47 @@
+#![cfg(feature = "eyre")]
+
+extern crate evitable;
+
+use evitable::*;
+
+#[evitable]
+pub enum Context {
+  #[evitable(description("Invalid port: {}", 0))]
+  InvalidPort(u16),
+
+  #[evitable(description = "Upstream failed", from = eyre::Report)]
+  Upstream,
+}
+
+fn parse(port: u16) -> Result<u16> {
+  ensure!(port != 0, Context::InvalidPort(port));
+  Ok(port)
+}
+
+fn run(port: u16) -> eyre::Result<u16> {
+  Ok(parse(port)?)
+}
+
+fn call_upstream() -> Result<()> {
+  Err(eyre::eyre!("connection refused"))?
+}
+
+#[test]
+fn evitable_kind() {
+  let error = run(0).unwrap_err().wrap_err("while starting");
+  assert_eq!(
+    error.evitable_kind::<ErrorKind>(),
+    Some(ErrorKind::InvalidPort)
+  );
+  assert_eq!(
+    error.evitable_error::<Error>().unwrap().to_string(),
+    "Invalid port: 0"
+  );
+}
+
+#[test]
+fn eyre_source() {
+  let error = call_upstream().unwrap_err();
+  assert_eq!(error.kind(), ErrorKind::Upstream);
+  assert_eq!(error.source().unwrap().to_string(), "connection refused");
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "failpoints")]
+extern crate evitable;
+
+use evitable::failpoints::{self, Trigger};
+use evitable::*;
+
+#[evitable]
+pub enum Context {
+  #[evitable(description = "Io error")]
+  Io,
+
+  #[evitable(description("Timed out after {} tries", 0))]
+  Timeout(u32),
+}
+
+fn read(point: &str) -> Result<&'static str> {
+  fail_point!(point, || Context::Io);
+  Ok("data")
+}
+
+fn connect(point: &str, tries: u32) -> Result<()> {
+  fail_point!(point, || Context::Timeout(tries));
+  Ok(())
+}
+
+#[test]
+fn inactive() {
+  assert_eq!(read("inactive").unwrap(), "data");
+  assert_eq!(failpoints::hits("inactive"), None);
+}
+
+#[test]
+fn always() {
+  failpoints::enable("always", Trigger::Always);
+  assert_eq!(read("always").unwrap_err().kind(), ErrorKind::Io);
+  assert_eq!(read("always").unwrap_err().kind(), ErrorKind::Io);
+  assert_eq!(failpoints::hits("always"), Some(2));
+
+  failpoints::disable("always");
+  assert_eq!(read("always").unwrap(), "data");
+}
+
+#[test]
+fn context_factory() {
+  failpoints::enable("factory", Trigger::Always);
+  let err = connect("factory", 3).unwrap_err();
+  assert_eq!(err.to_string(), "Timed out after 3 tries");
+}
+
+#[test]
+fn after() {
+  failpoints::enable("after", Trigger::After(2));
+  assert!(read("after").is_ok());
+  assert!(read("after").is_ok());
+  assert!(read("after").is_err());
+  assert!(read("after").is_err());
+}
+
+#[test]
+fn fraction() {
+  failpoints::enable("never", Trigger::Fraction(0.0));
+  failpoints::enable("half", Trigger::Fraction(0.5));
+  let mut failures = 0;
+  for _ in 0..1000 {
+    assert!(read("never").is_ok());
+    if read("half").is_err() {
+      failures += 1;
+    }
+  }
+
+  assert!(failures > 300 && failures < 700, "{} failures", failures);
+}
+
+#[test]
+fn configure() {
+  failpoints::configure("cfg_a; cfg_b=after(1); cfg_c=0%").unwrap();
+  assert!(read("cfg_a").is_err());
+  assert!(read("cfg_b").is_ok());
+  assert!(read("cfg_b").is_err());
+  assert!(read("cfg_c").is_ok());
+
+  assert!(failpoints::configure("cfg_d=sometimes").is_err());
+  assert!(failpoints::configure("cfg_e;=50%").is_err());
+  assert_eq!(failpoints::hits("cfg_e"), None);
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "ffi")]
+
+extern crate evitable;
+
+use evitable::ffi::*;
+use evitable::*;
+use std::os::raw::c_char;
+use std::ptr;
+
+#[evitable(ffi_code = 10)]
+pub enum Context {
+  #[evitable(description = "Invalid handle", ffi_code = 2)]
+  InvalidHandle,
+
+  #[evitable(description = "Negative code", ffi_code = "-3")]
+  Negative,
+
+  #[evitable(description = "Negative literal code", ffi_code = -4)]
+  NegativeLiteral,
+
+  #[evitable(description("Buffer too small: {}", 0))]
+  BufferTooSmall(usize),
+}
+
+mod unmapped {
+  use super::*;
+
+  #[evitable(description = "Something broke")]
+  pub struct Context;
+}
+
+fn close(handle: u32) -> i32 {
+  ffi_guard(|| -> Result<()> {
+    ensure!(handle != 0, Context::InvalidHandle);
+    Ok(())
+  })
+}
+
+fn message() -> (isize, String) {
+  let mut buf = [0 as c_char; 64];
+  let n = unsafe { evitable_last_error_message(buf.as_mut_ptr(), buf.len()) };
+  let bytes: Vec<u8> = buf
+    .iter()
+    .take_while(|c| **c != 0)
+    .map(|c| *c as u8)
+    .collect();
+  (n, String::from_utf8(bytes).unwrap())
+}
+
+#[test]
+fn ffi_code() {
+  assert_eq!(Context::InvalidHandle.ffi_code(), 2);
+  assert_eq!(ErrorKind::Negative.ffi_code(), -3);
+  assert_eq!(ErrorKind::NegativeLiteral.ffi_code(), -4);
+  assert_eq!(
+    Error::from_context(Context::BufferTooSmall(3)).ffi_code(),
+    10
+  );
+  assert_eq!(
+    unmapped::Error::from_context(unmapped::Context).ffi_code(),
+    DEFAULT_CODE
+  );
+  assert_ne!(DEFAULT_CODE, PANIC_CODE);
+}
+
+#[test]
+fn guard() {
+  assert_eq!(close(0), 2);
+  assert_eq!(evitable_last_error_code(), 2);
+  assert_eq!(last_error_message().as_deref(), Some("Invalid handle"));
+  assert_eq!(message(), (14, "Invalid handle".to_owned()));
+
+  assert_eq!(close(1), SUCCESS);
+  assert_eq!(evitable_last_error_code(), SUCCESS);
+  assert_eq!(message(), (0, String::new()));
+}
+
+#[test]
+fn clear() {
+  assert_eq!(close(0), 2);
+  evitable_clear_error();
+  assert_eq!(evitable_last_error_code(), SUCCESS);
+  assert_eq!(last_error_message(), None);
+}
+
+#[test]
+fn message_without_error() {
+  evitable_clear_error();
+  assert_eq!(
+    unsafe { evitable_last_error_message(ptr::null_mut(), 0) },
+    0
+  );
+
+  let mut buf = [1 as c_char; 4];
+  assert_eq!(
+    unsafe { evitable_last_error_message(buf.as_mut_ptr(), buf.len()) },
+    0
+  );
+  assert_eq!(buf[0], 0);
+}
+
+#[test]
+fn message_buffer_too_small() {
+  let error = Error::from_context(Context::BufferTooSmall(3));
+  assert_eq!(set_last_error(&error), 10);
+
+  let mut buf = [1 as c_char; 4];
+  let n = unsafe { evitable_last_error_message(buf.as_mut_ptr(), buf.len()) };
+  assert_eq!(n, -20);
+  assert_eq!(buf, [1; 4]);
+  assert_eq!(
+    unsafe { evitable_last_error_message(ptr::null_mut(), 0) },
+    -20
+  );
+}
+
+#[test]
+fn panics() {
+  let code = ffi_guard::<(), Error>(|| panic!("boom"));
+  assert_eq!(code, PANIC_CODE);
+  assert_eq!(last_error_message().as_deref(), Some("panic: boom"));
+}
+
+pub struct Explosive;
+
+impl std::fmt::Display for Explosive {
+  fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    panic!("display")
+  }
+}
+
+impl Drop for Explosive {
+  fn drop(&mut self) {
+    if !std::thread::panicking() {
+      panic!("drop")
+    }
+  }
+}
+
+mod explosive {
+  use super::*;
+
+  #[evitable(description("Exploded: {}", 0))]
+  pub struct Context(pub Explosive);
+}
+
+#[test]
+fn panics_while_formatting() {
+  let code = ffi_guard(|| -> explosive::Result<()> { Err(explosive::Context(Explosive).into()) });
+  assert_eq!(code, PANIC_CODE);
+  assert_eq!(last_error_message().as_deref(), Some("panic: display"));
+}
+
+#[test]
+fn panics_while_dropping() {
+  let code = ffi_guard(|| -> Result<Explosive> { Ok(Explosive) });
+  assert_eq!(code, PANIC_CODE);
+  assert_eq!(last_error_message().as_deref(), Some("panic: drop"));
+}
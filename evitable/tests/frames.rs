@@ -0,0 +1,57 @@
+extern crate evitable;
+
+use evitable::*;
+use std::io;
+
+#[evitable]
+pub enum ParseContext {
+  #[evitable(description = "Io error", from = io::Error)]
+  Io,
+
+  #[evitable(description("Invalid token at {}", 0))]
+  InvalidToken(usize),
+}
+
+#[evitable(description = "Plugin error")]
+pub struct PluginContext;
+
+fn parse() -> ParseResult<()> {
+  Err(ParseContext::InvalidToken(3))?
+}
+
+#[test]
+fn add_context_keeps_type() {
+  fn load() -> (ParseResult<()>, u32) {
+    let line = line!() + 2;
+    let result = parse()
+      .add_context(|| "while loading plugin foo")
+      .add_context(|| PluginContext);
+    (result, line)
+  }
+
+  let (result, line) = load();
+  let err: ParseError = result.unwrap_err();
+  assert_eq!(err.kind(), ParseErrorKind::InvalidToken);
+
+  let frames: Vec<_> = err.frames().iter().map(|f| f.to_string()).collect();
+  assert_eq!(frames, vec!["while loading plugin foo", "Plugin error"]);
+  let location = err.frames().iter().next().unwrap().location();
+  assert_eq!(location.file(), file!());
+  assert_eq!(location.line(), line);
+}
+
+#[test]
+fn display_renders_frames_in_order() {
+  let err = ParseError::from(io::Error::from(io::ErrorKind::NotFound))
+    .add_context("first")
+    .add_context("second");
+
+  let display = err.to_string();
+  assert!(display.starts_with("Io error\n  first\n  second\n---- source ----\n"));
+
+  let debug = format!("{:?}", err);
+  let first = debug.find("first").unwrap();
+  let second = debug.find("second").unwrap();
+  let source = debug.find("---- source ----").unwrap();
+  assert!(first < second && second < source);
+}
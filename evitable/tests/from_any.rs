@@ -0,0 +1,103 @@
+extern crate evitable;
+
+use evitable::*;
+use std::io;
+
+#[evitable]
+pub enum Context {
+  #[evitable(description = "Io", from = io::Error)]
+  Io,
+
+  #[evitable(description = "Other", from_any)]
+  Other,
+}
+
+#[test]
+fn or_other() {
+  fn fail() -> Result<u8> {
+    let value = "foo".parse::<u8>().or_other()?;
+
+    Ok(value)
+  }
+
+  let err = fail().unwrap_err();
+  assert_eq!(err.kind(), evitable_context::ErrorKind::Other);
+  assert!(err
+    .source()
+    .unwrap()
+    .downcast_ref::<std::num::ParseIntError>()
+    .is_some());
+}
+
+#[test]
+fn from_impls_still_apply() {
+  fn fail() -> Result<()> {
+    Err(io::Error::from(io::ErrorKind::NotFound))?;
+
+    Ok(())
+  }
+
+  let err = fail().unwrap_err();
+  assert_eq!(err.kind(), evitable_context::ErrorKind::Io);
+}
+
+#[test]
+fn from_any_error() {
+  fn inner() -> std::result::Result<(), AnyError> {
+    "foo".parse::<u8>()?;
+
+    Ok(())
+  }
+
+  fn fail() -> Result<()> {
+    inner()?;
+
+    Ok(())
+  }
+
+  let err = fail().unwrap_err();
+  assert_eq!(err.kind(), evitable_context::ErrorKind::Other);
+}
+
+#[test]
+fn from_boxed() {
+  fn fail() -> Result<()> {
+    let boxed: Box<dyn std::error::Error + Send + Sync> = "boxed".into();
+    Err(boxed)?;
+
+    Ok(())
+  }
+
+  let err = fail().unwrap_err();
+  assert_eq!(err.kind(), evitable_context::ErrorKind::Other);
+  assert_eq!(err.source().unwrap().to_string(), "boxed");
+}
+
+#[test]
+fn from_string() {
+  fn fail() -> Result<()> {
+    Err(format!("code {}", 42))?;
+
+    Ok(())
+  }
+
+  let err = fail().unwrap_err();
+  assert_eq!(err.kind(), evitable_context::ErrorKind::Other);
+  assert_eq!(err.source().unwrap().to_string(), "code 42");
+}
+
+mod unit_struct {
+  use super::*;
+
+  #[evitable(description = "Any", from_any)]
+  pub(super) struct Test;
+
+  #[test]
+  fn or_other() {
+    let err: Error = Err::<(), _>(io::Error::from(io::ErrorKind::NotFound))
+      .or_other()
+      .map_err(Error::from)
+      .unwrap_err();
+    assert!(err.source().is_some());
+  }
+}
@@ -0,0 +1,74 @@
+extern crate evitable;
+
+use evitable::*;
+
+mod storage {
+  use super::*;
+
+  #[evitable]
+  pub enum StorageContext {
+    #[evitable(description = "Not found")]
+    NotFound,
+
+    #[evitable(description("Corrupt block {}", block))]
+    Corrupt {
+      #[evitable(include_in_kind)]
+      block: u32,
+    },
+
+    #[evitable(description = "Io")]
+    Io,
+  }
+}
+
+mod api {
+  use super::*;
+
+  #[evitable(from_evitable(
+    storage::StorageError,
+    NotFound => ApiContext::NotFound,
+    Corrupt { block } => ApiContext::Internal { code: block },
+    _ => ApiContext::Internal { code: 0 },
+  ))]
+  pub enum ApiContext {
+    #[evitable(description = "Not found")]
+    NotFound,
+
+    #[evitable(description("Internal error ({})", code))]
+    Internal {
+      #[evitable(include_in_kind)]
+      code: u32,
+    },
+  }
+}
+
+use api::*;
+use storage::*;
+
+fn load(context: StorageContext) -> ApiResult<()> {
+  Err(StorageError::from(context))?;
+
+  Ok(())
+}
+
+#[test]
+fn maps_unit_kind() {
+  let err = load(StorageContext::NotFound).unwrap_err();
+  assert_eq!(err.kind(), ApiErrorKind::NotFound);
+  assert!(err.source().is_some());
+}
+
+#[test]
+fn maps_kind_with_fields() {
+  let err = load(StorageContext::Corrupt { block: 7 }).unwrap_err();
+  assert_eq!(err.kind(), ApiErrorKind::Internal { code: 7 });
+}
+
+#[test]
+fn maps_wildcard() {
+  let err = load(StorageContext::Io).unwrap_err();
+  assert_eq!(err.kind(), ApiErrorKind::Internal { code: 0 });
+
+  let source = err.source().unwrap().downcast_ref::<StorageError>();
+  assert_eq!(source.unwrap().kind(), StorageErrorKind::Io);
+}
@@ -39,3 +39,42 @@ mod all_enum {
     Utf8(),
   }
 }
+
+mod type_values {
+  use super::*;
+
+  #[evitable]
+  pub(super) enum Test {
+    #[evitable(description = "Message", from = &'static str)]
+    Message,
+
+    #[evitable(description = "Boxed", from = Box<dyn std::error::Error + Send + Sync>)]
+    Boxed,
+
+    #[evitable(description = "Io", from = IoError)]
+    Io,
+  }
+
+  #[test]
+  fn reference_type() {
+    let err = Error::from("static message");
+    assert_eq!(err.kind(), evitable_test::ErrorKind::Message);
+    assert_eq!(err.source().unwrap().to_string(), "static message");
+  }
+
+  #[test]
+  fn boxed_type() {
+    let boxed: Box<dyn std::error::Error + Send + Sync> =
+      IoError::from(std::io::ErrorKind::NotFound).into();
+    let err = Error::from(boxed);
+    assert_eq!(err.kind(), evitable_test::ErrorKind::Boxed);
+    assert!(err.source().unwrap().downcast_ref::<IoError>().is_some());
+  }
+
+  #[test]
+  fn path_type() {
+    let err = Error::from(IoError::from(std::io::ErrorKind::NotFound));
+    assert_eq!(err.kind(), evitable_test::ErrorKind::Io);
+    assert!(err.source().unwrap().downcast_ref::<IoError>().is_some());
+  }
+}
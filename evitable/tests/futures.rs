@@ -0,0 +1,134 @@
+#![cfg(feature = "futures")]
+extern crate evitable;
+
+use evitable::futures::{FutureExt, TryStreamExt};
+use evitable::*;
+use futures_core::Stream;
+use std::cell::Cell;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+#[evitable(description("Failed to fetch {}", 0))]
+pub struct FetchContext(String);
+
+fn block_on<F: Future>(future: F) -> F::Output {
+  let mut future = Box::pin(future);
+  let mut cx = Context::from_waker(Waker::noop());
+  loop {
+    if let Poll::Ready(v) = future.as_mut().poll(&mut cx) {
+      return v;
+    }
+  }
+}
+
+fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+  let mut cx = Context::from_waker(Waker::noop());
+  loop {
+    if let Poll::Ready(v) = Pin::new(&mut *stream).poll_next(&mut cx) {
+      return v;
+    }
+  }
+}
+
+struct Iter<I>(I);
+
+impl<I: Iterator + Unpin> Stream for Iter<I> {
+  type Item = I::Item;
+
+  fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<I::Item>> {
+    Poll::Ready(self.0.next())
+  }
+}
+
+async fn fetch(url: &str) -> io::Result<String> {
+  match url {
+    "missing" => Err(io::Error::from(io::ErrorKind::NotFound)),
+    _ => Ok(url.to_owned()),
+  }
+}
+
+#[test]
+fn future_context() {
+  let ok = block_on(fetch("ok").context(|| FetchContext("ok".to_owned())));
+  assert_eq!(ok.unwrap(), "ok");
+
+  let err = block_on(fetch("missing").context(|| FetchContext("missing".to_owned()))).unwrap_err();
+  assert_eq!(err.context().0, "missing");
+  assert!(err.source().unwrap().downcast_ref::<io::Error>().is_some());
+}
+
+#[test]
+fn future_context_is_lazy() {
+  let called = Cell::new(false);
+  let result = block_on(fetch("ok").context(|| {
+    called.set(true);
+    FetchContext("ok".to_owned())
+  }));
+  assert!(result.is_ok());
+  assert!(!called.get());
+}
+
+#[test]
+fn future_context_with() {
+  let err = block_on(fetch("missing").context_with(|e| FetchContext(format!("{:?}", e.kind()))))
+    .unwrap_err();
+  assert_eq!(err.context().0, "NotFound");
+}
+
+#[test]
+fn stream_context() {
+  let items = vec![Ok(1), Err(io::Error::from(io::ErrorKind::NotFound)), Ok(3)];
+  let calls = Cell::new(0);
+  let mut stream = Iter(items.into_iter()).context(|| {
+    calls.set(calls.get() + 1);
+    FetchContext("item".to_owned())
+  });
+
+  assert_eq!(next(&mut stream).unwrap().unwrap(), 1);
+  assert_eq!(calls.get(), 0);
+  assert_eq!(next(&mut stream).unwrap().unwrap_err().context().0, "item");
+  assert_eq!(next(&mut stream).unwrap().unwrap(), 3);
+  assert!(next(&mut stream).is_none());
+  assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn stream_context_with() {
+  let items: Vec<io::Result<i32>> = vec![Err(io::Error::from(io::ErrorKind::TimedOut))];
+  let mut stream =
+    Iter(items.into_iter()).context_with(|e| FetchContext(format!("{:?}", e.kind())));
+
+  assert_eq!(
+    next(&mut stream).unwrap().unwrap_err().context().0,
+    "TimedOut"
+  );
+}
+
+#[test]
+fn location_is_caller() {
+  let future = fetch("missing").context(|| FetchContext("missing".to_owned()));
+  let line = line!() - 1;
+  let err = block_on(future).unwrap_err();
+  assert_eq!(err.location().file(), file!());
+  assert_eq!(err.location().line(), line);
+
+  let future = fetch("missing").context_with(|_| FetchContext("missing".to_owned()));
+  let line = line!() - 1;
+  let err = block_on(future).unwrap_err();
+  assert_eq!(err.location().line(), line);
+
+  let results = vec![Err::<(), _>(io::Error::from(io::ErrorKind::NotFound))];
+  let mut stream = Iter(results.into_iter()).context(|| FetchContext("stream".to_owned()));
+  let line = line!() - 1;
+  let err: FetchError = next(&mut stream).unwrap().unwrap_err();
+  assert_eq!(err.location().file(), file!());
+  assert_eq!(err.location().line(), line);
+
+  let results = vec![Err::<(), _>(io::Error::from(io::ErrorKind::NotFound))];
+  let mut stream = Iter(results.into_iter()).context_with(|_| FetchContext("stream".to_owned()));
+  let line = line!() - 1;
+  let err: FetchError = next(&mut stream).unwrap().unwrap_err();
+  assert_eq!(err.location().line(), line);
+}
@@ -0,0 +1,100 @@
+extern crate evitable;
+
+use evitable::grpc::Code;
+use evitable::*;
+
+#[evitable(grpc_code = "InvalidArgument")]
+pub enum Context {
+  #[evitable(
+    description("User {} not found", id),
+    grpc_code = NotFound,
+    code = "USER_NOT_FOUND"
+  )]
+  UserNotFound {
+    #[evitable(include_in_kind)]
+    id: u64,
+  },
+
+  #[evitable(description = "Invalid request")]
+  Invalid,
+
+  #[evitable(description = "Database is down", grpc_code = "Unavailable")]
+  Database,
+}
+
+mod unmapped {
+  use super::*;
+
+  #[evitable(description = "Something broke")]
+  pub struct Context;
+}
+
+#[test]
+fn code() {
+  assert_eq!(Context::UserNotFound { id: 1 }.grpc_code(), Code::NotFound);
+  assert_eq!(ErrorKind::Invalid.grpc_code(), Code::InvalidArgument);
+  assert_eq!(
+    Error::from_context(Context::Database).grpc_code(),
+    Code::Unavailable
+  );
+  assert_eq!(
+    unmapped::Error::from_context(unmapped::Context).grpc_code(),
+    Code::Unknown
+  );
+  assert_eq!(Code::NotFound.as_i32(), 5);
+  assert_eq!(Code::NotFound.to_string(), "NotFound");
+}
+
+#[test]
+fn kind_from_name() {
+  assert_eq!(ErrorKind::from_name("Invalid"), Some(ErrorKind::Invalid));
+  assert_eq!(ErrorKind::from_name("UserNotFound"), None);
+  assert_eq!(ErrorKind::from_name("Unknown"), None);
+  assert_eq!(
+    unmapped::ErrorKind::from_name("Context"),
+    Some(unmapped::ErrorKind::Context)
+  );
+}
+
+#[cfg(feature = "tonic")]
+mod tonic_status {
+  use super::*;
+  use evitable::grpc::{kind_from_status, CODE_METADATA_KEY, KIND_METADATA_KEY};
+
+  #[test]
+  fn status() {
+    let status = tonic::Status::from(Error::from_context(Context::UserNotFound { id: 42 }));
+    assert_eq!(status.code(), tonic::Code::NotFound);
+    assert_eq!(status.message(), "User 42 not found");
+    assert_eq!(
+      status.metadata().get(KIND_METADATA_KEY).unwrap(),
+      "UserNotFound"
+    );
+    assert_eq!(
+      status.metadata().get(CODE_METADATA_KEY).unwrap(),
+      "USER_NOT_FOUND"
+    );
+  }
+
+  #[test]
+  fn status_without_code() {
+    let status = tonic::Status::from(Error::from_context(Context::Database));
+    assert_eq!(status.code(), tonic::Code::Unavailable);
+    assert!(status.metadata().get(CODE_METADATA_KEY).is_none());
+  }
+
+  #[test]
+  fn reverse() {
+    let status = tonic::Status::from(Error::from_context(Context::Invalid));
+    assert_eq!(
+      kind_from_status::<ErrorKind>(&status),
+      Some(ErrorKind::Invalid)
+    );
+
+    let status = tonic::Status::from(Error::from_context(Context::UserNotFound { id: 1 }));
+    assert_eq!(kind_from_status::<ErrorKind>(&status), None);
+
+    let status = tonic::Status::not_found("elsewhere");
+    assert_eq!(kind_from_status::<ErrorKind>(&status), None);
+  }
+}
@@ -0,0 +1,125 @@
+extern crate evitable;
+
+use evitable::*;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[evitable]
+pub enum Context {
+  #[evitable(description("Not found: {}", 0), code = "NF", severity = "info")]
+  NotFound(&'static str),
+
+  #[evitable(description = "Io error", from = io::Error)]
+  Io,
+
+  #[evitable(description = "Only seen by the global hook")]
+  Global,
+}
+
+#[derive(Debug, PartialEq)]
+struct Seen {
+  kind: &'static str,
+  code: Option<&'static str>,
+  severity: Severity,
+  context: String,
+  line: u32,
+}
+
+fn record(f: impl FnOnce()) -> Vec<Seen> {
+  let seen = Arc::new(Mutex::new(Vec::new()));
+  let hook_seen = seen.clone();
+  with_hook(
+    move |info: &dyn ErrorInfo| {
+      assert!(info.type_name().ends_with("Error"));
+      hook_seen.lock().unwrap().push(Seen {
+        kind: info.kind_name(),
+        code: info.code(),
+        severity: info.severity(),
+        context: info.context().to_string(),
+        line: info.location().line(),
+      });
+    },
+    f,
+  );
+
+  Arc::try_unwrap(seen).unwrap().into_inner().unwrap()
+}
+
+#[test]
+fn local_hook() {
+  let line = line!() + 2;
+  let seen = record(|| {
+    let _ = Error::from_context(Context::NotFound("user"));
+    let _ = Error::from(io::Error::from(io::ErrorKind::Other));
+  });
+
+  assert_eq!(
+    seen,
+    vec![
+      Seen {
+        kind: "NotFound",
+        code: Some("NF"),
+        severity: Severity::Info,
+        context: "Not found: user".to_owned(),
+        line,
+      },
+      Seen {
+        kind: "Io",
+        code: None,
+        severity: Severity::Error,
+        context: "Io error".to_owned(),
+        line: line + 1,
+      },
+    ]
+  );
+}
+
+#[test]
+fn local_hook_is_restored() {
+  let outer = record(|| {
+    let inner = record(|| {
+      let _ = Error::from_context(Context::Io);
+    });
+    assert_eq!(inner.len(), 1);
+    let _ = Error::from_context(Context::NotFound("outer"));
+  });
+
+  assert_eq!(outer.len(), 1);
+  assert_eq!(outer[0].kind, "NotFound");
+}
+
+#[test]
+fn errors_in_hook_are_ignored() {
+  let count = Arc::new(AtomicUsize::new(0));
+  let hook_count = count.clone();
+  with_hook(
+    move |_: &dyn ErrorInfo| {
+      hook_count.fetch_add(1, Ordering::SeqCst);
+      let _ = Error::from_context(Context::Io);
+    },
+    || Error::from_context(Context::Io),
+  );
+
+  assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn global_hook() {
+  static COUNT: AtomicUsize = AtomicUsize::new(0);
+  set_hook(|info: &dyn ErrorInfo| {
+    if info.kind_name() == "Global" {
+      COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+  });
+
+  let _ = Error::from_context(Context::Global);
+  let _ = Error::from_context(Context::Global);
+  std::thread::spawn(|| Error::from_context(Context::Global))
+    .join()
+    .unwrap();
+  clear_hook();
+  let _ = Error::from_context(Context::Global);
+
+  assert_eq!(COUNT.load(Ordering::SeqCst), 3);
+}
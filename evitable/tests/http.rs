@@ -0,0 +1,179 @@
+extern crate evitable;
+
+use evitable::http::ErrorBody;
+use evitable::*;
+use std::future::Future;
+use std::task::{Context as TaskContext, Poll, Waker};
+
+#[evitable(http_status = 400)]
+pub enum Context {
+  #[evitable(
+    description("User {} not found", 0),
+    http_status = 404,
+    code = "USER_NOT_FOUND"
+  )]
+  UserNotFound(u64),
+
+  #[evitable(description = "Invalid request")]
+  Invalid,
+
+  #[evitable(description("Database at {} is down", 0), http_status = 503)]
+  Database(&'static str),
+}
+
+mod internal {
+  use super::*;
+
+  #[evitable(description = "Something broke")]
+  pub struct Context;
+}
+
+#[allow(dead_code)]
+mod user_facing {
+  use super::*;
+
+  #[evitable(http_message = "user")]
+  pub enum Context {
+    #[evitable(
+      description("Card {} declined by gateway", 0),
+      user_message("Your card ending in {} was declined", 0),
+      http_status = 402
+    )]
+    Declined(u16),
+
+    #[evitable(
+      description("Database at {} is down", 0),
+      user_message = "Please try again later"
+    )]
+    Database(&'static str),
+  }
+}
+
+#[allow(dead_code)]
+fn block_on<F: Future>(future: F) -> F::Output {
+  let mut future = Box::pin(future);
+  let mut cx = TaskContext::from_waker(Waker::noop());
+  loop {
+    if let Poll::Ready(v) = future.as_mut().poll(&mut cx) {
+      return v;
+    }
+  }
+}
+
+#[test]
+fn status() {
+  assert_eq!(Context::UserNotFound(1).http_status(), 404);
+  assert_eq!(ErrorKind::Invalid.http_status(), 400);
+  assert_eq!(
+    Error::from_context(Context::Database("db")).http_status(),
+    503
+  );
+  assert_eq!(
+    internal::Error::from_context(internal::Context).http_status(),
+    500
+  );
+}
+
+#[test]
+fn body() {
+  let body = ErrorBody::new(&Error::from_context(Context::UserNotFound(42)));
+  assert_eq!(body.status(), 404);
+  assert_eq!(body.kind(), "UserNotFound");
+  assert_eq!(body.code(), Some("USER_NOT_FOUND"));
+  assert_eq!(body.message(), "User 42 not found");
+}
+
+#[test]
+fn body_hides_internal_details() {
+  let error = Error::from_context(Context::Database("10.0.0.1"));
+  let body = ErrorBody::new(&error);
+  assert_eq!(body.kind(), "Database");
+  assert_eq!(body.message(), evitable::http::INTERNAL_ERROR_MESSAGE);
+
+  let body = ErrorBody::with_details(&error);
+  assert_eq!(body.message(), "Database at 10.0.0.1 is down");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json() {
+  let body = ErrorBody::new(&Error::from_context(Context::UserNotFound(42)));
+  assert_eq!(
+    String::from_utf8(body.to_json()).unwrap(),
+    r#"{"kind":"UserNotFound","code":"USER_NOT_FOUND","message":"User 42 not found"}"#
+  );
+
+  let body = ErrorBody::new(&Error::from_context(Context::Invalid));
+  assert_eq!(
+    String::from_utf8(body.to_json()).unwrap(),
+    r#"{"kind":"Invalid","message":"Invalid request"}"#
+  );
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn axum_response() {
+  use axum::response::IntoResponse;
+
+  let response = Error::from_context(Context::UserNotFound(42)).into_response();
+  assert_eq!(response.status().as_u16(), 404);
+  assert_eq!(
+    response.headers()["content-type"].to_str().unwrap(),
+    "application/json"
+  );
+
+  let body = block_on(axum::body::to_bytes(response.into_body(), 1024)).unwrap();
+  assert_eq!(
+    &body[..],
+    br#"{"kind":"UserNotFound","code":"USER_NOT_FOUND","message":"User 42 not found"}"#
+  );
+}
+
+#[cfg(feature = "actix-web")]
+#[test]
+fn actix_web_response() {
+  use actix_web::ResponseError;
+
+  let error = Error::from_context(Context::Database("10.0.0.1"));
+  assert_eq!(error.status_code().as_u16(), 503);
+
+  let response = error.error_response();
+  assert_eq!(response.status().as_u16(), 503);
+  let body = block_on(actix_web::body::to_bytes(response.into_body())).unwrap();
+  assert_eq!(
+    &body[..],
+    br#"{"kind":"Database","message":"Internal server error"}"#
+  );
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn axum_user_message() {
+  use axum::response::IntoResponse;
+
+  let error = user_facing::Error::from_context(user_facing::Context::Declined(4242));
+  let response = error.into_response();
+  assert_eq!(response.status().as_u16(), 402);
+
+  let body = block_on(axum::body::to_bytes(response.into_body(), 1024)).unwrap();
+  assert_eq!(
+    &body[..],
+    br#"{"kind":"Declined","message":"Your card ending in 4242 was declined"}"#
+  );
+}
+
+#[cfg(feature = "actix-web")]
+#[test]
+fn actix_web_user_message() {
+  use actix_web::ResponseError;
+
+  let error = user_facing::Error::from_context(user_facing::Context::Database("10.0.0.1"));
+  let response = error.error_response();
+  assert_eq!(response.status().as_u16(), 500);
+
+  let body = block_on(actix_web::body::to_bytes(response.into_body())).unwrap();
+  assert_eq!(
+    &body[..],
+    br#"{"kind":"Database","message":"Please try again later"}"#
+  );
+}
@@ -0,0 +1,71 @@
+extern crate evitable;
+
+use evitable::*;
+use std::io;
+
+#[evitable(io_kind = InvalidData)]
+pub enum Context {
+  #[evitable(description("File {} not found", 0), io_kind = NotFound)]
+  NotFound(&'static str),
+
+  #[evitable(description = "Corrupt header")]
+  CorruptHeader,
+
+  #[evitable(description = "Connection lost", io_kind = "ConnectionReset")]
+  ConnectionLost,
+}
+
+mod unmapped {
+  use super::*;
+
+  #[evitable(description = "Something broke")]
+  pub struct Context;
+}
+
+fn read_header(fail: bool) -> io::Result<()> {
+  if fail {
+    Err(Error::from_context(Context::CorruptHeader))?;
+  }
+
+  Ok(())
+}
+
+#[test]
+fn io_kind() {
+  assert_eq!(Context::NotFound("a").io_kind(), io::ErrorKind::NotFound);
+  assert_eq!(
+    ErrorKind::CorruptHeader.io_kind(),
+    io::ErrorKind::InvalidData
+  );
+  assert_eq!(
+    Error::from_context(Context::ConnectionLost).io_kind(),
+    io::ErrorKind::ConnectionReset
+  );
+  assert_eq!(
+    unmapped::Error::from_context(unmapped::Context).io_kind(),
+    io::ErrorKind::Other
+  );
+}
+
+#[test]
+fn into_io_error() {
+  let error = io::Error::from(Error::from_context(Context::NotFound("a.txt")));
+  assert_eq!(error.kind(), io::ErrorKind::NotFound);
+  assert_eq!(error.to_string(), "File a.txt not found");
+
+  let error = read_header(true).unwrap_err();
+  assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+  assert!(read_header(false).is_ok());
+}
+
+#[test]
+fn from_io() {
+  let error = io::Error::from(Error::from_context(Context::NotFound("a.txt")));
+  let inner = Error::from_io(&error).unwrap();
+  assert_eq!(inner.kind(), ErrorKind::NotFound);
+  assert!(unmapped::Error::from_io(&error).is_none());
+
+  let error = io::Error::new(io::ErrorKind::NotFound, "plain");
+  assert!(Error::from_io(&error).is_none());
+  assert!(Error::from_io(&io::Error::from(io::ErrorKind::Other)).is_none());
+}
@@ -0,0 +1,69 @@
+extern crate evitable;
+
+use evitable::*;
+use std::convert::TryFrom;
+
+#[evitable(kind_repr = u16)]
+pub enum Context {
+  #[evitable(description = "Timeout", discriminant = 42)]
+  Timeout,
+
+  #[evitable(description("Invalid frame: {}", len), discriminant = 7)]
+  InvalidFrame {
+    #[evitable(include_in_kind)]
+    len: usize,
+  },
+
+  #[evitable(description("Closed by peer: {}", 0), discriminant = 0x100)]
+  Closed(&'static str),
+}
+
+mod signed {
+  use super::*;
+
+  #[evitable(description = "Unavailable", kind_repr = i8, discriminant = "-1")]
+  pub struct Context;
+}
+
+mod wide {
+  use super::*;
+
+  #[evitable(
+    description = "Overflow",
+    kind_repr = u64,
+    discriminant = 18446744073709551615
+  )]
+  pub struct Context;
+}
+
+#[test]
+fn as_repr() {
+  assert_eq!(ErrorKind::Timeout.as_repr(), 42u16);
+  assert_eq!(ErrorKind::InvalidFrame { len: 3 }.as_repr(), 7);
+  assert_eq!(Context::Closed("bye").kind().as_repr(), 256);
+  assert_eq!(signed::ErrorKind::Context.as_repr(), -1i8);
+  assert_eq!(wide::ErrorKind::Context.as_repr(), u64::MAX);
+  assert_eq!(
+    wide::ErrorKind::try_from(u64::MAX),
+    Ok(wide::ErrorKind::Context)
+  );
+}
+
+#[test]
+fn try_from() {
+  assert_eq!(ErrorKind::try_from(42), Ok(ErrorKind::Timeout));
+  assert_eq!(ErrorKind::try_from(256), Ok(ErrorKind::Closed));
+  assert_eq!(ErrorKind::try_from(7), Err(7));
+  assert_eq!(ErrorKind::try_from(1), Err(1));
+  assert_eq!(
+    signed::ErrorKind::try_from(-1),
+    Ok(signed::ErrorKind::Context)
+  );
+}
+
+#[test]
+fn round_trip() {
+  for kind in [ErrorKind::Timeout, ErrorKind::Closed] {
+    assert_eq!(ErrorKind::try_from(kind.as_repr()), Ok(kind));
+  }
+}
@@ -0,0 +1,73 @@
+extern crate evitable;
+
+use evitable::*;
+use std::io;
+
+#[evitable]
+pub enum Context {
+  #[evitable(description = "Io", from = io::Error)]
+  Io,
+
+  #[evitable(description = "Missing")]
+  Missing,
+}
+
+fn io_error() -> io::Error {
+  io::Error::from(io::ErrorKind::NotFound)
+}
+
+#[test]
+fn from_context() {
+  let err = Error::from_context(Context::Missing);
+  assert_eq!(err.location().file(), file!());
+  assert_eq!(err.location().line(), line!() - 2);
+}
+
+#[test]
+fn from_question_mark() {
+  fn fail() -> Result<()> {
+    Err(io_error())?;
+
+    Ok(())
+  }
+
+  let err = fail().unwrap_err();
+  assert_eq!(err.location().line(), line!() - 6);
+}
+
+#[test]
+fn option_context() {
+  let err = None::<()>.context(|| Context::Missing).unwrap_err();
+  assert_eq!(err.location().line(), line!() - 1);
+}
+
+#[test]
+fn result_context_with() {
+  let err = Err::<(), _>(io_error())
+    .context_with(|_| Context::Io)
+    .unwrap_err();
+  assert_eq!(err.location().line(), line!() - 2);
+}
+
+#[test]
+fn into_error() {
+  let err = Context::Io.into_error(io_error());
+  assert_eq!(err.location().line(), line!() - 1);
+}
+
+#[test]
+fn fail_macro() {
+  fn fail() -> Result<()> {
+    fail!(Context::Missing);
+  }
+
+  let err = fail().unwrap_err();
+  assert_eq!(err.location().line(), line!() - 4);
+}
+
+#[test]
+fn debug_includes_location() {
+  let err = Error::from(Context::Missing);
+  let debug = format!("{:?}", err);
+  assert!(debug.contains(&format!("at {}:{}:", file!(), line!() - 2)));
+}
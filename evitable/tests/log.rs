@@ -0,0 +1,97 @@
+#![cfg(feature = "log")]
+extern crate evitable;
+
+use evitable::*;
+use log::kv::Key;
+use log::{Level, Log, Metadata, Record};
+use std::cell::RefCell;
+
+#[evitable(severity = "warn")]
+pub enum Context {
+  #[evitable(description = "Not found", severity = "info", code = "NOT_FOUND")]
+  NotFound,
+
+  #[evitable(description = "Corruption", severity = "error")]
+  Corruption,
+
+  #[evitable(description = "Timeout")]
+  Timeout,
+}
+
+struct Entry {
+  level: Level,
+  message: String,
+  kind: Option<String>,
+  code: Option<String>,
+}
+
+thread_local! {
+  static ENTRIES: RefCell<Vec<Entry>> = const { RefCell::new(Vec::new()) };
+}
+
+struct Logger;
+
+impl Log for Logger {
+  fn enabled(&self, _: &Metadata<'_>) -> bool {
+    true
+  }
+
+  fn log(&self, record: &Record<'_>) {
+    let kv = record.key_values();
+    let get = |key| kv.get(Key::from_str(key)).map(|v| v.to_string());
+    let entry = Entry {
+      level: record.level(),
+      message: record.args().to_string(),
+      kind: get("kind"),
+      code: get("code"),
+    };
+
+    ENTRIES.with(|e| e.borrow_mut().push(entry));
+  }
+
+  fn flush(&self) {}
+}
+
+fn capture(f: impl FnOnce()) -> Vec<Entry> {
+  static LOGGER: Logger = Logger;
+  let _ = log::set_logger(&LOGGER);
+  log::set_max_level(log::LevelFilter::Trace);
+  f();
+  ENTRIES.with(|e| e.borrow_mut().drain(..).collect())
+}
+
+#[test]
+fn log_at_severity() {
+  let entries = capture(|| {
+    Error::from_context(Context::NotFound).log();
+    Error::from_context(Context::Corruption).log();
+    Error::from_context(Context::Timeout).log();
+  });
+
+  let levels: Vec<_> = entries.iter().map(|e| e.level).collect();
+  assert_eq!(levels, vec![Level::Info, Level::Error, Level::Warn]);
+  assert_eq!(entries[0].message, "Not found");
+  assert_eq!(entries[0].kind.as_deref(), Some("NotFound"));
+  assert_eq!(entries[0].code.as_deref(), Some("NOT_FOUND"));
+  assert_eq!(entries[1].kind.as_deref(), Some("Corruption"));
+}
+
+#[test]
+fn log_err() {
+  fn load(fail: bool) -> Result<u8> {
+    if fail {
+      Err(Context::Timeout)?;
+    }
+
+    Ok(1)
+  }
+
+  let entries = capture(|| {
+    assert_eq!(load(false).log_err().unwrap(), 1);
+    assert!(load(true).log_err().is_err());
+  });
+
+  assert_eq!(entries.len(), 1);
+  assert_eq!(entries[0].level, Level::Warn);
+  assert_eq!(entries[0].message, "Timeout");
+}
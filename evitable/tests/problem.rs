@@ -0,0 +1,116 @@
+#![cfg(feature = "serde")]
+
+extern crate evitable;
+
+use evitable::problem::{Problem, ProblemRenderer, BLANK_TYPE, CONTENT_TYPE};
+use evitable::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Opaque(u8);
+
+#[evitable(http_status = 400)]
+pub enum Context {
+  #[evitable(
+    description("Out of credit, balance is {}", balance),
+    http_status = 403
+  )]
+  OutOfCredit {
+    #[evitable(include_in_kind)]
+    balance: u32,
+    #[evitable(include_in_kind)]
+    account: &'static str,
+  },
+
+  #[evitable(description = "Invalid token")]
+  InvalidToken {
+    #[evitable(include_in_kind)]
+    token: Opaque,
+  },
+
+  #[evitable(description("Database at {} is down", 0), http_status = 503)]
+  Database(&'static str),
+}
+
+fn json(problem: &Problem) -> String {
+  String::from_utf8(problem.to_json()).unwrap()
+}
+
+#[test]
+fn members() {
+  let error = Error::from_context(Context::OutOfCredit {
+    balance: 30,
+    account: "/account/12345",
+  });
+  let problem = Problem::new(&error);
+  assert_eq!(problem.type_uri(), "#OutOfCredit");
+  assert_eq!(problem.title(), "OutOfCredit");
+  assert_eq!(problem.status(), 403);
+  assert_eq!(problem.detail(), Some("Out of credit, balance is 30"));
+  assert_eq!(
+    json(&problem),
+    r##"{"type":"#OutOfCredit","title":"OutOfCredit","status":403,"detail":"Out of credit, balance is 30","account":"/account/12345","balance":30}"##
+  );
+}
+
+#[test]
+fn type_template() {
+  let error = Error::from_context(Context::InvalidToken { token: Opaque(1) });
+  let problem = ProblemRenderer::new()
+    .type_template("https://example.com/probs/{kind}")
+    .render(&error);
+  assert_eq!(problem.type_uri(), "https://example.com/probs/InvalidToken");
+
+  let problem = ProblemRenderer::new()
+    .type_template("https://example.com/probs/{kind}")
+    .render(&Error::from_context(Context::Database("10.0.0.1")));
+  assert_eq!(problem.type_uri(), BLANK_TYPE);
+}
+
+#[test]
+fn title_is_stable() {
+  let first = Problem::new(&Error::from_context(Context::OutOfCredit {
+    balance: 30,
+    account: "/account/1",
+  }));
+  let second = Problem::new(&Error::from_context(Context::OutOfCredit {
+    balance: 10,
+    account: "/account/2",
+  }));
+  assert_eq!(first.title(), second.title());
+  assert_ne!(first.detail(), second.detail());
+}
+
+#[test]
+fn debug_fallback() {
+  let error = Error::from_context(Context::InvalidToken { token: Opaque(7) });
+  let problem = Problem::new(&error);
+  assert_eq!(problem.status(), 400);
+  assert_eq!(problem.extensions()["token"], "Opaque(7)");
+}
+
+#[test]
+fn detail_includes_frames() {
+  let error = Error::from_context(Context::InvalidToken { token: Opaque(1) })
+    .add_context("while authenticating");
+  let problem = Problem::new(&error);
+  assert_eq!(problem.title(), "InvalidToken");
+  assert_eq!(
+    problem.detail(),
+    Some("Invalid token\n  while authenticating")
+  );
+}
+
+#[test]
+fn hides_internal_details() {
+  let error = Error::from_context(Context::Database("10.0.0.1"));
+  let problem = Problem::new(&error);
+  assert_eq!(problem.type_uri(), BLANK_TYPE);
+  assert_eq!(problem.title(), evitable::http::INTERNAL_ERROR_MESSAGE);
+  assert_eq!(problem.detail(), None);
+  assert!(problem.extensions().is_empty());
+}
+
+#[test]
+fn content_type() {
+  assert_eq!(CONTENT_TYPE, "application/problem+json");
+}
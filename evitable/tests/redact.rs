@@ -0,0 +1,106 @@
+extern crate evitable;
+
+use evitable::*;
+
+#[evitable]
+pub enum Context {
+  #[evitable(description(
+    "Authentication failed for {} (hint: {})",
+    user,
+    unredacted(password_hint)
+  ))]
+  AuthFailed {
+    user: String,
+    #[evitable(redact)]
+    password_hint: String,
+  },
+
+  #[evitable(description("Invalid token {:?}", 0))]
+  InvalidToken(#[evitable(redact = "***")] &'static str),
+
+  #[evitable(description("Account {} locked", account), http_status = 423)]
+  Locked {
+    #[evitable(include_in_kind, redact)]
+    account: u64,
+    #[evitable(include_in_kind)]
+    attempts: u32,
+  },
+}
+
+fn auth_failed() -> Error {
+  Error::from_context(Context::AuthFailed {
+    user: "admin".to_owned(),
+    password_hint: "first pet".to_owned(),
+  })
+}
+
+#[test]
+fn display_and_debug() {
+  let error = auth_failed();
+  assert_eq!(
+    error.context().to_string(),
+    "Authentication failed for admin (hint: <redacted>)"
+  );
+  assert_eq!(
+    format!("{:?}", error.context()),
+    "Authentication failed for admin (hint: <redacted>)"
+  );
+  assert!(!format!("{:?}", error).contains("first pet"));
+  assert!(!error.to_string().contains("first pet"));
+
+  let context = Context::InvalidToken("secret");
+  assert_eq!(context.to_string(), "Invalid token ***");
+}
+
+#[test]
+fn unredacted() {
+  let error = auth_failed();
+  assert_eq!(
+    error.context().unredacted().to_string(),
+    "Authentication failed for admin (hint: first pet)"
+  );
+
+  let error = error.add_context("while logging in");
+  assert_eq!(
+    error.unredacted().to_string(),
+    "Authentication failed for admin (hint: first pet)\n  while logging in"
+  );
+
+  // Only fields referenced as `unredacted(field)` are shown.
+  let context = Context::InvalidToken("secret");
+  assert_eq!(context.unredacted().to_string(), "Invalid token ***");
+}
+
+#[test]
+fn kind() {
+  let kind = Context::Locked {
+    account: 1234,
+    attempts: 3,
+  }
+  .kind();
+  assert_eq!(
+    kind,
+    ErrorKind::Locked {
+      account: 1234,
+      attempts: 3
+    }
+  );
+  assert_eq!(
+    format!("{:?}", kind),
+    "Locked { account: <redacted>, attempts: 3 }"
+  );
+  assert_eq!(format!("{:?}", ErrorKind::AuthFailed), "AuthFailed");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn problem() {
+  let error = Error::from_context(Context::Locked {
+    account: 1234,
+    attempts: 3,
+  });
+  let problem = evitable::problem::Problem::new(&error);
+  assert_eq!(problem.detail(), Some("Account <redacted> locked"));
+  assert_eq!(problem.extensions()["account"], "<redacted>");
+  assert_eq!(problem.extensions()["attempts"], 3);
+}
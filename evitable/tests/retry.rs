@@ -0,0 +1,177 @@
+extern crate evitable;
+
+use evitable::retry::{self, Backoff};
+use evitable::*;
+use std::cell::RefCell;
+use std::future::Future;
+use std::task::{Context as TaskContext, Poll, Waker};
+use std::time::Duration;
+
+#[evitable]
+pub enum Context {
+  #[evitable(description = "Service unavailable", retryable)]
+  Unavailable,
+
+  #[evitable(description("Rate limited for {:?}", 0), retryable)]
+  RateLimited(#[evitable(retry_after)] Duration),
+
+  #[evitable(description = "Throttled", retryable)]
+  Throttled {
+    #[evitable(retry_after)]
+    delay: Option<Duration>,
+  },
+
+  #[evitable(description = "Not found")]
+  NotFound,
+}
+
+mod transient {
+  use super::*;
+
+  #[evitable(retryable)]
+  pub enum Context {
+    #[evitable(description = "Timeout")]
+    Timeout,
+
+    #[evitable(description = "Invalid request", retryable = false)]
+    Invalid,
+  }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+  let mut future = Box::pin(future);
+  let mut cx = TaskContext::from_waker(Waker::noop());
+  loop {
+    if let Poll::Ready(v) = future.as_mut().poll(&mut cx) {
+      return v;
+    }
+  }
+}
+
+fn no_delay() -> Backoff {
+  Backoff::new()
+    .initial_delay(Duration::from_millis(0))
+    .max_attempts(5)
+}
+
+#[test]
+fn classification() {
+  assert!(Context::Unavailable.is_retryable());
+  assert!(!Context::NotFound.is_retryable());
+  assert!(ErrorKind::RateLimited.is_retryable());
+  assert!(!ErrorKind::NotFound.is_retryable());
+  assert!(Error::from_context(Context::Unavailable).is_retryable());
+
+  assert!(transient::Context::Timeout.is_retryable());
+  assert!(!transient::Context::Invalid.is_retryable());
+}
+
+#[test]
+fn retry_after() {
+  let delay = Duration::from_secs(3);
+  assert_eq!(Context::RateLimited(delay).retry_after(), Some(delay));
+  assert_eq!(
+    Context::Throttled { delay: Some(delay) }.retry_after(),
+    Some(delay)
+  );
+  assert_eq!(Context::Throttled { delay: None }.retry_after(), None);
+  assert_eq!(Context::Unavailable.retry_after(), None);
+  assert_eq!(
+    Error::from_context(Context::RateLimited(delay)).retry_after(),
+    Some(delay)
+  );
+}
+
+#[test]
+fn retries_until_success() {
+  let mut attempts = 0;
+  let result = no_delay().retry(|| {
+    attempts += 1;
+    if attempts < 3 {
+      Err(Error::from_context(Context::Unavailable))
+    } else {
+      Ok(attempts)
+    }
+  });
+
+  assert_eq!(result.unwrap(), 3);
+}
+
+#[test]
+fn stops_on_permanent_error() {
+  let mut attempts = 0;
+  let result: Result<()> = no_delay().retry(|| {
+    attempts += 1;
+    Err(Error::from_context(Context::NotFound))
+  });
+
+  assert_eq!(result.unwrap_err().kind(), ErrorKind::NotFound);
+  assert_eq!(attempts, 1);
+}
+
+#[test]
+fn stops_after_max_attempts() {
+  let mut attempts = 0;
+  let result: Result<()> = no_delay().max_attempts(4).retry(|| {
+    attempts += 1;
+    Err(Error::from_context(Context::Unavailable))
+  });
+
+  assert!(result.is_err());
+  assert_eq!(attempts, 4);
+}
+
+#[test]
+fn default_retry() {
+  let mut attempts = 0;
+  let result = retry::retry(|| {
+    attempts += 1;
+    if attempts < 2 {
+      Err(Error::from_context(Context::RateLimited(
+        Duration::from_millis(1),
+      )))
+    } else {
+      Ok(())
+    }
+  });
+
+  assert!(result.is_ok());
+  assert_eq!(attempts, 2);
+}
+
+#[test]
+fn async_backoff() {
+  let delays = RefCell::new(Vec::new());
+  let attempts = RefCell::new(0);
+  let backoff = Backoff::new()
+    .initial_delay(Duration::from_millis(10))
+    .max_delay(Duration::from_millis(30))
+    .max_attempts(5);
+
+  let result: Result<()> = block_on(backoff.retry_async(
+    || {
+      *attempts.borrow_mut() += 1;
+      let context = match *attempts.borrow() {
+        3 => Context::RateLimited(Duration::from_secs(1)),
+        _ => Context::Unavailable,
+      };
+      async move { Err(Error::from_context(context)) }
+    },
+    |delay| {
+      delays.borrow_mut().push(delay);
+      async {}
+    },
+  ));
+
+  assert!(result.is_err());
+  assert_eq!(*attempts.borrow(), 5);
+  assert_eq!(
+    *delays.borrow(),
+    vec![
+      Duration::from_millis(10),
+      Duration::from_millis(20),
+      Duration::from_secs(1),
+      Duration::from_millis(30),
+    ]
+  );
+}
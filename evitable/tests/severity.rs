@@ -0,0 +1,89 @@
+extern crate evitable;
+
+use evitable::*;
+
+mod storage {
+  use super::*;
+
+  #[evitable(severity = "warn")]
+  pub enum StorageContext {
+    #[evitable(description = "Not found", severity = "info", code = "S404")]
+    NotFound,
+
+    #[evitable(
+      description("Corrupted block {}", 0),
+      severity = "error",
+      code = "S500"
+    )]
+    Corruption(u64),
+
+    #[evitable(description = "Timeout")]
+    Timeout,
+  }
+}
+
+mod plain {
+  use super::*;
+
+  #[evitable]
+  pub enum Context {
+    #[evitable(description = "Io error")]
+    Io,
+  }
+}
+
+mod single {
+  use super::*;
+
+  #[evitable(description = "Rate limited", severity = "debug", code = "RATE")]
+  pub struct Context;
+}
+
+use storage::*;
+
+#[test]
+fn context() {
+  assert_eq!(StorageContext::NotFound.severity(), Severity::Info);
+  assert_eq!(StorageContext::Corruption(3).severity(), Severity::Error);
+  assert_eq!(StorageContext::Timeout.severity(), Severity::Warn);
+
+  assert_eq!(StorageContext::NotFound.code(), Some("S404"));
+  assert_eq!(StorageContext::Timeout.code(), None);
+}
+
+#[test]
+fn kind() {
+  assert_eq!(StorageErrorKind::NotFound.severity(), Severity::Info);
+  assert_eq!(StorageErrorKind::Corruption.severity(), Severity::Error);
+  assert_eq!(StorageErrorKind::Timeout.severity(), Severity::Warn);
+  assert_eq!(StorageErrorKind::Corruption.code(), Some("S500"));
+}
+
+#[test]
+fn error() {
+  let err = StorageError::from_context(StorageContext::Corruption(3));
+  assert_eq!(err.severity(), Severity::Error);
+  assert_eq!(err.code(), Some("S500"));
+}
+
+#[test]
+fn defaults() {
+  let err = plain::Error::from_context(plain::Context::Io);
+  assert_eq!(err.severity(), Severity::Error);
+  assert_eq!(err.code(), None);
+}
+
+#[test]
+fn structs() {
+  let err = single::Error::from_context(single::Context);
+  assert_eq!(err.severity(), Severity::Debug);
+  assert_eq!(err.kind().severity(), Severity::Debug);
+  assert_eq!(err.code(), Some("RATE"));
+}
+
+#[test]
+fn ordering() {
+  assert!(Severity::Warn < Severity::Error);
+  assert!(Severity::Trace < Severity::Info);
+  assert_eq!(Severity::Warn.to_string(), "warn");
+}
@@ -0,0 +1,19 @@
+extern crate evitable;
+
+use evitable::*;
+use std::mem::size_of;
+
+#[evitable(description = "Error")]
+pub struct Context;
+
+#[test]
+fn empty_storage_is_pointer_sized() {
+  assert_eq!(size_of::<Frames>(), size_of::<usize>());
+  assert_eq!(size_of::<Attachments>(), size_of::<usize>());
+}
+
+#[test]
+fn error_is_small() {
+  // Results with larger errors are flagged by `clippy::result_large_err`.
+  assert!(size_of::<Error>() <= 128);
+}
@@ -0,0 +1,80 @@
+extern crate evitable;
+
+use evitable::testing::*;
+use evitable::*;
+use std::io;
+
+#[evitable]
+pub enum Context {
+  #[evitable(description = "Io error", from = io::Error)]
+  Io,
+
+  #[evitable(description("Invalid token. Expected {}.", expected))]
+  InvalidToken { expected: &'static str },
+}
+
+fn io_error() -> Result<()> {
+  Err(io::Error::new(io::ErrorKind::NotFound, "config.toml"))?
+}
+
+fn token_error() -> Result<()> {
+  Err(Context::InvalidToken { expected: "EOF" })?
+}
+
+#[test]
+fn kind() {
+  assert_kind!(io_error(), ErrorKind::Io);
+  assert_kind!(token_error(), ErrorKind::InvalidToken);
+}
+
+#[test]
+#[should_panic(expected = "expected an error of kind `ErrorKind::Io`, got `InvalidToken`")]
+fn kind_mismatch() {
+  assert_kind!(token_error(), ErrorKind::Io);
+}
+
+#[test]
+#[should_panic(expected = "got `Ok`")]
+fn kind_ok() {
+  let result: Result<()> = Ok(());
+  assert_kind!(result, ErrorKind::Io);
+}
+
+#[test]
+fn err_matches() {
+  assert_err_matches!(io_error(), Context::Io);
+  assert_err_matches!(token_error(), Context::InvalidToken { .. });
+  assert_err_matches!(token_error(), Context::Io | Context::InvalidToken { .. });
+  assert_err_matches!(
+    token_error(),
+    Context::InvalidToken { expected, .. } if *expected == "EOF"
+  );
+}
+
+#[test]
+#[should_panic(expected = "Invalid token. Expected EOF.")]
+fn err_matches_guard_fails() {
+  assert_err_matches!(
+    token_error(),
+    Context::InvalidToken { expected } if *expected == "let"
+  );
+}
+
+#[test]
+fn source() {
+  let err = io_error().unwrap_err();
+  let source = assert_source!(err, io::Error);
+  assert_eq!(source.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+#[should_panic(expected = "expected a source of type `io::Error`")]
+fn source_missing() {
+  assert_source!(token_error().unwrap_err(), io::Error);
+}
+
+#[test]
+#[should_panic(expected = "config.toml")]
+fn message_includes_chain() {
+  assert_err_matches!(io_error(), Context::InvalidToken { .. });
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "tracing")]
+extern crate evitable;
+
+use evitable::*;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{info_span, Event, Subscriber};
+use tracing_error::ErrorLayer;
+use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{Layer, Registry};
+
+#[evitable]
+pub enum Context {
+  #[evitable(description("Failed to load {}", 0))]
+  Load(&'static str),
+
+  #[evitable(description = "Io error")]
+  Io,
+}
+
+#[derive(Default)]
+struct Fields(Vec<(String, String)>);
+
+impl Visit for Fields {
+  fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    self
+      .0
+      .push((field.name().to_owned(), format!("{:?}", value)));
+  }
+}
+
+#[derive(Clone, Default)]
+struct Recorded {
+  spans: Arc<Mutex<Vec<(String, String)>>>,
+  events: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Recorded {
+  fn on_new_span(&self, _: &Attributes<'_>, _: &Id, _: LayerContext<'_, S>) {}
+
+  fn on_record(&self, _: &Id, values: &Record<'_>, _: LayerContext<'_, S>) {
+    let mut fields = Fields::default();
+    values.record(&mut fields);
+    self.spans.lock().unwrap().extend(fields.0);
+  }
+
+  fn on_event(&self, event: &Event<'_>, _: LayerContext<'_, S>) {
+    let mut fields = Fields::default();
+    event.record(&mut fields);
+    self.events.lock().unwrap().extend(fields.0);
+  }
+}
+
+fn get(fields: &Mutex<Vec<(String, String)>>, name: &str) -> Option<String> {
+  fields
+    .lock()
+    .unwrap()
+    .iter()
+    .find(|(n, _)| n == name)
+    .map(|(_, v)| v.clone())
+}
+
+#[test]
+fn no_subscriber() {
+  let err = Error::from_context(Context::Io);
+  assert!(err.span_trace().is_empty());
+  assert_eq!(err.span_trace().to_string(), "");
+}
+
+#[test]
+fn captures_spans() {
+  let subscriber = Registry::default().with(ErrorLayer::default());
+  tracing::subscriber::with_default(subscriber, || {
+    let span = info_span!("load_config", path = "config.toml");
+    let _guard = span.enter();
+    let err = Error::from_context(Context::Load("config.toml"));
+
+    assert!(!err.span_trace().is_empty());
+    let trace = err.span_trace().to_string();
+    assert!(trace.contains("load_config"), "{}", trace);
+    assert!(trace.contains("config.toml"), "{}", trace);
+  });
+}
+
+#[test]
+fn record_in_current_span() {
+  let recorded = Recorded::default();
+  let subscriber = Registry::default().with(recorded.clone());
+  tracing::subscriber::with_default(subscriber, || {
+    let span = info_span!(
+      "load_config",
+      error.kind = tracing::field::Empty,
+      error.message = tracing::field::Empty
+    );
+    let _guard = span.enter();
+    Error::from_context(Context::Load("config.toml")).record_in_current_span();
+  });
+
+  assert_eq!(get(&recorded.spans, "error.kind").unwrap(), "Load");
+  assert_eq!(
+    get(&recorded.spans, "error.message").unwrap(),
+    "Failed to load config.toml"
+  );
+  assert_eq!(get(&recorded.events, "error.kind").unwrap(), "Load");
+  assert_eq!(
+    get(&recorded.events, "message").unwrap(),
+    "Failed to load config.toml"
+  );
+}
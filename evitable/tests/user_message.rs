@@ -0,0 +1,209 @@
+extern crate evitable;
+
+use evitable::http::{self, ErrorBody};
+use evitable::*;
+
+#[evitable(user_message = "Something went wrong, please try again later")]
+pub enum Context {
+  #[evitable(
+    description("Card {} declined by gateway: {}", card, reason),
+    user_message("Your card ending in {} was declined", card),
+    http_status = 402
+  )]
+  Declined { card: u16, reason: String },
+
+  #[evitable(
+    description("Promo code {} not found in {}", 0, 1),
+    user_message("Promo code {} is not valid", 0),
+    http_status = 404
+  )]
+  InvalidPromo(&'static str, &'static str),
+
+  #[evitable(
+    description("Account {} suspended", account),
+    user_message("Account {} is suspended", account)
+  )]
+  Suspended {
+    #[evitable(redact)]
+    account: u64,
+  },
+
+  #[evitable(description = "Connection pool exhausted")]
+  PoolExhausted,
+}
+
+mod plain {
+  use evitable::*;
+
+  #[evitable]
+  pub enum Context {
+    #[evitable(description = "Disk full", user_message = "Not enough storage")]
+    DiskFull,
+
+    #[evitable(description = "Cache poisoned")]
+    CachePoisoned,
+  }
+}
+
+#[evitable(
+  description("Upload of {} failed after {} bytes", file, bytes),
+  user_message("Could not upload {}", file)
+)]
+pub struct UploadContext {
+  file: &'static str,
+  bytes: u64,
+}
+
+#[evitable(description = "Lock poisoned")]
+pub struct LockContext;
+
+fn declined() -> Error {
+  Error::from_context(Context::Declined {
+    card: 4242,
+    reason: "do_not_honor".to_owned(),
+  })
+}
+
+#[test]
+fn variant_messages() {
+  let error = declined();
+  assert_eq!(
+    error.user_message().to_string(),
+    "Your card ending in 4242 was declined"
+  );
+  assert_eq!(
+    error.context().to_string(),
+    "Card 4242 declined by gateway: do_not_honor"
+  );
+
+  let context = Context::InvalidPromo("SUMMER", "promos_2019");
+  assert_eq!(
+    context.user_message().to_string(),
+    "Promo code SUMMER is not valid"
+  );
+}
+
+#[test]
+fn redacted_fields() {
+  let context = Context::Suspended { account: 1234 };
+  assert_eq!(
+    context.user_message().to_string(),
+    "Account <redacted> is suspended"
+  );
+}
+
+#[test]
+fn fallback_messages() {
+  assert_eq!(
+    Context::PoolExhausted.user_message().to_string(),
+    "Something went wrong, please try again later"
+  );
+  assert_eq!(
+    plain::Context::DiskFull.user_message().to_string(),
+    "Not enough storage"
+  );
+  assert_eq!(
+    plain::Context::CachePoisoned.user_message().to_string(),
+    DEFAULT_USER_MESSAGE
+  );
+  assert_eq!(LockContext.user_message().to_string(), DEFAULT_USER_MESSAGE);
+}
+
+#[test]
+fn struct_message() {
+  let error = UploadError::from_context(UploadContext {
+    file: "avatar.png",
+    bytes: 512,
+  });
+  assert_eq!(
+    error.user_message().to_string(),
+    "Could not upload avatar.png"
+  );
+}
+
+#[test]
+fn message_render() {
+  let error = declined();
+  assert_eq!(
+    Message::Description.render(error.context()),
+    "Card 4242 declined by gateway: do_not_honor"
+  );
+  assert_eq!(
+    Message::User.render(error.context()),
+    "Your card ending in 4242 was declined"
+  );
+  assert_eq!(Message::default(), Message::Description);
+}
+
+#[test]
+fn http_body() {
+  let body = ErrorBody::with_user_message(&declined());
+  assert_eq!(body.status(), 402);
+  assert_eq!(body.message(), "Your card ending in 4242 was declined");
+
+  let error = Error::from_context(Context::PoolExhausted);
+  let body = ErrorBody::with_user_message(&error);
+  assert_eq!(body.status(), 500);
+  assert_eq!(
+    body.message(),
+    "Something went wrong, please try again later"
+  );
+
+  assert_eq!(
+    ErrorBody::new_with(&error, Message::User).message(),
+    "Something went wrong, please try again later"
+  );
+  assert_eq!(
+    ErrorBody::new_with(&error, Message::Description).message(),
+    http::INTERNAL_ERROR_MESSAGE
+  );
+  assert_eq!(
+    ErrorBody::new_with(&declined(), Message::Description).message(),
+    "Card 4242 declined by gateway: do_not_honor"
+  );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn problem() {
+  use evitable::problem::ProblemRenderer;
+
+  let renderer = ProblemRenderer::new().message(Message::User);
+  let problem = renderer.render(&declined().add_context("while checking out"));
+  assert_eq!(problem.type_uri(), "#Declined");
+  assert_eq!(problem.title(), "Declined");
+  assert_eq!(problem.status(), 402);
+  assert_eq!(
+    problem.detail(),
+    Some("Your card ending in 4242 was declined")
+  );
+
+  let problem = renderer.render(&Error::from_context(Context::PoolExhausted));
+  assert_eq!(problem.type_uri(), evitable::problem::BLANK_TYPE);
+  assert_eq!(problem.title(), http::INTERNAL_ERROR_MESSAGE);
+  assert_eq!(
+    problem.detail(),
+    Some("Something went wrong, please try again later")
+  );
+  assert_eq!(problem.status(), 500);
+  assert!(problem.extensions().is_empty());
+}
+
+#[test]
+fn exit_result() {
+  let result = ExitResult::from(declined()).message(Message::User);
+  assert_eq!(result.exit_code(), 1);
+  assert_eq!(
+    result.report_text().as_deref(),
+    Some("Error: Your card ending in 4242 was declined")
+  );
+  assert!(result.into_result().is_err());
+
+  let result = ExitResult::from(declined().add_context("while checking out"));
+  assert_eq!(
+    result.report_text().as_deref(),
+    Some("Error: Card 4242 declined by gateway: do_not_honor\n  while checking out")
+  );
+
+  assert_eq!(ExitResult::<Error>::ok().report_text(), None);
+}
@@ -4,10 +4,10 @@ use std::rc::Rc;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use syn::spanned::Spanned;
-use syn::{Ident, Lit, Path};
+use syn::{Ident, Lit, Path, Type};
 
 use super::error::{Error, Result};
-use super::{Meta, MetaValue, NestedMeta};
+use super::{Meta, MetaArm, MetaValue, NestedMeta};
 
 pub trait FromMeta: Sized {
   fn from_nested_meta(item: &NestedMeta) -> Result<Self> {
@@ -18,6 +18,7 @@ pub trait FromMeta: Sized {
         Meta::NameValue(_) => Err(Error::unexpected_type("name value").with_span(item)),
         Meta::List(_) => Err(Error::unexpected_type("list").with_span(item)),
       },
+      NestedMeta::Arm(arm) => Self::from_arm(arm),
     })
     .map_err(|e| e.with_span(item))
   }
@@ -68,6 +69,7 @@ pub trait FromMeta: Sized {
     (match value {
       MetaValue::Path(path) => Self::from_path(path),
       MetaValue::Literal(lit) => Self::from_lit(lit),
+      MetaValue::Type(ty) => Self::from_type(ty),
     })
     .map_err(|e| e.with_span(value))
   }
@@ -124,6 +126,18 @@ pub trait FromMeta: Sized {
   fn from_ident(value: &Ident) -> Result<Self> {
     Err(Error::unexpected_type("ident"))
   }
+
+  /// Create an instance from a type (that isn't a plain path) in a value position.
+  #[allow(unused_variables)]
+  fn from_type(value: &Type) -> Result<Self> {
+    Err(Error::unexpected_type("type"))
+  }
+
+  /// Create an instance from a match arm (`pat => expr`) in a list position.
+  #[allow(unused_variables)]
+  fn from_arm(arm: &MetaArm) -> Result<Self> {
+    Err(Error::unexpected_type("match arm"))
+  }
 }
 
 // FromMeta impls for std and syn types.
@@ -196,11 +210,13 @@ from_meta_num!(u8);
 from_meta_num!(u16);
 from_meta_num!(u32);
 from_meta_num!(u64);
+from_meta_num!(u128);
 from_meta_num!(usize);
 from_meta_num!(i8);
 from_meta_num!(i16);
 from_meta_num!(i32);
 from_meta_num!(i64);
+from_meta_num!(i128);
 from_meta_num!(isize);
 
 /// Generate an impl of `FromMeta` that will accept strings which parse to floats or
@@ -263,6 +279,25 @@ impl FromMeta for syn::Path {
   }
 }
 
+/// Parsing support for types. Plain paths are accepted as path types, and
+/// strings are parsed as types.
+impl FromMeta for syn::Type {
+  fn from_path(path: &Path) -> Result<Self> {
+    Ok(syn::Type::Path(syn::TypePath {
+      qself: None,
+      path: path.clone(),
+    }))
+  }
+
+  fn from_type(value: &Type) -> Result<Self> {
+    Ok(value.clone())
+  }
+
+  fn from_string<S: Spanned>(value: &str, span: &S) -> Result<Self> {
+    syn::parse_str(value).map_err(|_| Error::unknown_value(value).with_span(span))
+  }
+}
+
 impl FromMeta for syn::Lit {
   fn from_lit(value: &Lit) -> Result<Self> {
     Ok(value.clone())
@@ -298,6 +333,12 @@ impl FromMeta for Meta {
   }
 }
 
+impl FromMeta for MetaArm {
+  fn from_arm(arm: &MetaArm) -> Result<Self> {
+    Ok(arm.clone())
+  }
+}
+
 impl FromMeta for ident_case::RenameRule {
   fn from_string<S: Spanned>(value: &str, span: &S) -> Result<Self> {
     value
@@ -441,6 +482,18 @@ mod tests {
     assert_eq!(fm::<f64>(quote!(ignore = 1.4e10)), 1.4e10f64);
   }
 
+  #[test]
+  fn type_succeeds() {
+    let ty: syn::Type = parse_quote!(&'static str);
+    assert_eq!(fm::<syn::Type>(quote!(ignore = &'static str)), ty);
+
+    let ty: syn::Type = parse_quote!(::std::io::Error);
+    assert_eq!(fm::<syn::Type>(quote!(ignore = ::std::io::Error)), ty);
+
+    let ty: syn::Type = parse_quote!((u8, String));
+    assert_eq!(fm::<syn::Type>(quote!(ignore = "(u8, String)")), ty);
+  }
+
   #[test]
   fn meta_succeeds() {
     assert_eq!(
@@ -5,11 +5,11 @@ extern crate quote;
 #[cfg_attr(test, macro_use)]
 extern crate syn;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::ToTokens;
 
 use syn::parse::*;
-use syn::{parenthesized, parse, token, Attribute, Ident, Lit, LitBool, Path, Token};
+use syn::{parenthesized, parse, token, Attribute, Ident, Lit, LitBool, LitInt, Path, Token};
 
 pub mod ast;
 pub mod error;
@@ -189,6 +189,17 @@ pub enum NestedMeta {
 
   /// A Rust literal, like the `"new_name"` in `#[rename("new_name")]`.
   Literal(syn::Lit),
+
+  /// A match arm, like the `NotFound => Context::NotFound` in
+  /// `#[evitable(from_evitable(Error, NotFound => Context::NotFound))]`.
+  Arm(Box<MetaArm>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetaArm {
+  pub pat: syn::Pat,
+  pub fat_arrow_token: syn::token::FatArrow,
+  pub body: syn::Expr,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -202,18 +213,25 @@ pub struct MetaNameValue {
 pub enum MetaValue {
   Path(syn::Path),
   Literal(syn::Lit),
+  /// Any type that isn't a plain path, like `&'static str` or `(u8, u8)`.
+  Type(Box<syn::Type>),
 }
 
 impl Parse for MetaValue {
   fn parse(input: ParseStream) -> Result<Self> {
     let ahead = input.fork();
 
-    if ahead.call(Path::parse).is_ok() {
-      input.parse().map(MetaValue::Path)
-    } else if ahead.call(Lit::parse).is_ok() {
+    if ahead.call(Lit::parse).is_ok() {
       input.parse().map(MetaValue::Literal)
+    } else if input.peek(Token![-]) && input.peek2(LitInt) {
+      parse_negative_int(input).map(MetaValue::Literal)
+    } else if ahead.call(syn::Type::parse).is_ok() {
+      match input.parse()? {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => Ok(MetaValue::Path(path)),
+        ty => Ok(MetaValue::Type(Box::new(ty))),
+      }
     } else {
-      Err(input.error("expected path or literal"))
+      Err(input.error("expected type, path or literal"))
     }
   }
 }
@@ -245,6 +263,8 @@ impl Parse for NestedMeta {
 
     if ahead.peek(Lit) && !(ahead.peek(LitBool) && ahead.peek2(Token![=])) {
       input.parse().map(NestedMeta::Literal)
+    } else if is_meta_arm(&input.fork()) {
+      input.parse().map(Box::new).map(NestedMeta::Arm)
     } else if ahead.call(Path::parse).is_ok() {
       input.parse().map(NestedMeta::Meta)
     } else {
@@ -253,6 +273,32 @@ impl Parse for NestedMeta {
   }
 }
 
+impl Parse for MetaArm {
+  fn parse(input: ParseStream) -> Result<Self> {
+    Ok(MetaArm {
+      pat: input.parse()?,
+      fat_arrow_token: input.parse()?,
+      body: input.parse()?,
+    })
+  }
+}
+
+fn is_meta_arm(ahead: ParseStream) -> bool {
+  ahead.parse::<syn::Pat>().is_ok() && ahead.peek(Token![=>])
+}
+
+/// Parses a negative integer literal like `-2`, which is a unary
+/// expression rather than a literal to `syn`.
+fn parse_negative_int(input: ParseStream) -> Result<Lit> {
+  input.parse::<Token![-]>()?;
+  let lit: LitInt = input.parse()?;
+  let mut token: Literal = format!("-{}", lit)
+    .parse()
+    .map_err(|_| syn::Error::new(lit.span(), "invalid negative literal"))?;
+  token.set_span(lit.span());
+  Ok(Lit::Int(LitInt::from(token)))
+}
+
 fn parse_meta_after_path(path: Path, input: ParseStream) -> Result<Meta> {
   if input.peek(token::Paren) {
     parse_meta_list_after_path(path, input).map(Meta::List)
@@ -285,6 +331,7 @@ impl ToTokens for MetaValue {
     match self {
       MetaValue::Literal(l) => l.to_tokens(tokens),
       MetaValue::Path(p) => p.to_tokens(tokens),
+      MetaValue::Type(t) => t.to_tokens(tokens),
     }
   }
 }
@@ -302,10 +349,19 @@ impl ToTokens for NestedMeta {
     match self {
       NestedMeta::Meta(meta) => meta.to_tokens(tokens),
       NestedMeta::Literal(lit) => lit.to_tokens(tokens),
+      NestedMeta::Arm(arm) => arm.to_tokens(tokens),
     }
   }
 }
 
+impl ToTokens for MetaArm {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    self.pat.to_tokens(tokens);
+    self.fat_arrow_token.to_tokens(tokens);
+    self.body.to_tokens(tokens);
+  }
+}
+
 impl ToTokens for MetaList {
   fn to_tokens(&self, tokens: &mut TokenStream) {
     self.path.to_tokens(tokens);
@@ -338,4 +394,72 @@ mod test {
     println!("{:?}", meta);
     assert_eq!(path_str, "evitable :: from");
   }
+
+  #[test]
+  pub fn negative_value_test() {
+    let input: syn::ItemStruct = parse_quote! { #[code = -2] struct Foo; };
+    let meta = input.attrs[0].meta().unwrap();
+    match &meta {
+      Meta::NameValue(MetaNameValue {
+        val: MetaValue::Literal(Lit::Int(lit)),
+        ..
+      }) => assert_eq!(lit.base10_parse::<i32>().unwrap(), -2),
+      _ => panic!("expected integer literal, got {:?}", meta),
+    }
+  }
+
+  #[test]
+  pub fn type_value_test() {
+    let input: syn::ItemStruct = parse_quote! { #[from = &'static str] struct Foo; };
+    let meta = input.attrs[0].meta().unwrap();
+    match &meta {
+      Meta::NameValue(MetaNameValue {
+        val: MetaValue::Type(ty),
+        ..
+      }) => match **ty {
+        syn::Type::Reference(_) => (),
+        _ => panic!("expected reference type, got {:?}", ty),
+      },
+      _ => panic!("expected type, got {:?}", meta),
+    }
+
+    let input: syn::ItemStruct = parse_quote! { #[from = Box<dyn Error + Send>] struct Foo; };
+    let meta = input.attrs[0].meta().unwrap();
+    match meta {
+      Meta::NameValue(MetaNameValue {
+        val: MetaValue::Path(_),
+        ..
+      }) => (),
+      _ => panic!("expected path, got {:?}", meta),
+    }
+  }
+
+  #[test]
+  pub fn arm_test() {
+    let input: syn::ItemStruct = parse_quote! { #[evitable(from_evitable(Error, NotFound => Context::NotFound, _ => Context::Other))] struct Foo; };
+    let meta = input.attrs[0].meta().unwrap();
+    let list = match meta {
+      Meta::List(list) => list,
+      _ => panic!("expected list"),
+    };
+    let inner = match list.nested.first() {
+      Some(NestedMeta::Meta(Meta::List(inner))) => inner.clone(),
+      _ => panic!("expected nested list"),
+    };
+
+    let nested: Vec<_> = inner.nested.iter().collect();
+    assert_eq!(nested.len(), 3);
+    if let NestedMeta::Arm(_) = nested[0] {
+      panic!("expected path");
+    }
+
+    let pats: Vec<_> = nested[1..]
+      .iter()
+      .map(|item| match item {
+        NestedMeta::Arm(arm) => format!("{}", arm.pat.to_token_stream()),
+        _ => panic!("expected arm"),
+      })
+      .collect();
+    assert_eq!(pats, vec!["NotFound", "_"]);
+  }
 }